@@ -15,12 +15,23 @@
 
 #![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
 
+// The `impl_sha_*` macros below are exported so that crates declaring their
+// own `ShaVariant` can generate C bindings with the same ABI, e.g.
+//
+//     c_sha2::impl_sha_init!(tagged_init, Tagged);
+//     c_sha2::impl_sha_update!(tagged_update, Tagged);
+//     c_sha2::impl_sha_finalize!(tagged_finalize, Tagged, TaggedCfg);
+//
+// They are feature-agnostic; callers gate each invocation themselves.
+#[doc(hidden)]
+pub use ::sha2 as __sha2;
+
+#[macro_export]
 macro_rules! impl_sha_init {
     ($fn_name:ident, $sha_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(feature = "streaming")]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(ctx: *mut $sha_ty, ctx_size: usize) -> usize {
             let size = ::core::mem::size_of::<$sha_ty>();
@@ -39,12 +50,12 @@ macro_rules! impl_sha_init {
     };
 }
 
+#[macro_export]
 macro_rules! impl_sha_reset {
     ($fn_name:ident, $sha_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(feature = "streaming")]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(ctx: *mut $sha_ty) {
             let ctx = unsafe { &mut *ctx };
@@ -53,12 +64,12 @@ macro_rules! impl_sha_reset {
     };
 }
 
+#[macro_export]
 macro_rules! impl_sha_update {
     ($fn_name:ident, $sha_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(feature = "streaming")]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(ctx: *mut $sha_ty, data: *const u8, data_size: usize) {
             if data.is_null() && data_size != 0 {
@@ -72,19 +83,19 @@ macro_rules! impl_sha_update {
     };
 }
 
+#[macro_export]
 macro_rules! impl_sha_finalize {
     ($fn_name:ident, $sha_ty:ty, $cfg_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(feature = "streaming")]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(
             ctx: *mut $sha_ty,
             result_ptr: *mut u8,
             result_size: usize,
         ) -> usize {
-            let size = <$cfg_ty>::DIGEST_BYTES;
+            let size = <$cfg_ty as $crate::__sha2::ShaVariant>::DIGEST_BYTES;
 
             if !result_ptr.is_null() {
                 if result_size < size {
@@ -102,12 +113,12 @@ macro_rules! impl_sha_finalize {
     };
 }
 
+#[macro_export]
 macro_rules! impl_sha_digest {
     ($fn_name:ident, $sha_ty:ty, $cfg_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(feature = "sync")]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(
             result_ptr: *mut u8,
@@ -115,7 +126,7 @@ macro_rules! impl_sha_digest {
             data: *const u8,
             data_size: usize,
         ) -> usize {
-            let size = <$cfg_ty>::DIGEST_BYTES;
+            let size = <$cfg_ty as $crate::__sha2::ShaVariant>::DIGEST_BYTES;
 
             if !result_ptr.is_null() {
                 if result_size < size {
@@ -133,12 +144,78 @@ macro_rules! impl_sha_digest {
     };
 }
 
+/// One-shot hashing of `count` messages described by parallel arrays of
+/// pointers and lengths, through `hash_batch` in groups of eight.
+#[macro_export]
+macro_rules! impl_sha_digest_batch {
+    ($fn_name:ident, $ty:ty, $cfg_ty:ty) => {
+        #[doc = "# Safety"]
+        #[doc = ""]
+        #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $fn_name(
+            result_ptr: *mut u8,
+            result_size: usize,
+            data_ptrs: *const *const u8,
+            data_sizes: *const usize,
+            count: usize,
+        ) -> usize {
+            const DIGEST_BYTES: usize = <$cfg_ty as $crate::__sha2::ShaVariant>::DIGEST_BYTES;
+            const GROUP: usize = 8;
+
+            let Some(size) = count.checked_mul(DIGEST_BYTES) else {
+                return 0;
+            };
+
+            if !result_ptr.is_null() {
+                if result_size < size {
+                    return 0;
+                }
+                if count == 0 {
+                    return size;
+                }
+                if data_ptrs.is_null() || data_sizes.is_null() {
+                    return 0;
+                }
+
+                let ptrs = unsafe { ::core::slice::from_raw_parts(data_ptrs, count) };
+                let sizes = unsafe { ::core::slice::from_raw_parts(data_sizes, count) };
+                let result_buf = unsafe { ::core::slice::from_raw_parts_mut(result_ptr, size) };
+
+                for ((ptrs, sizes), out) in ptrs
+                    .chunks(GROUP)
+                    .zip(sizes.chunks(GROUP))
+                    .zip(result_buf.chunks_mut(GROUP * DIGEST_BYTES))
+                {
+                    let mut inputs: [&[u8]; GROUP] = [&[]; GROUP];
+                    for (input, (&ptr, &len)) in inputs.iter_mut().zip(ptrs.iter().zip(sizes)) {
+                        if len != 0 {
+                            if ptr.is_null() {
+                                return 0;
+                            }
+                            *input = unsafe { ::core::slice::from_raw_parts(ptr, len) };
+                        }
+                    }
+
+                    let mut digests = [[0u8; DIGEST_BYTES]; GROUP];
+                    <$ty>::hash_batch(&inputs[..ptrs.len()], &mut digests[..ptrs.len()]);
+                    for (dst, digest) in out.chunks_exact_mut(DIGEST_BYTES).zip(&digests) {
+                        dst.copy_from_slice(digest);
+                    }
+                }
+            }
+
+            size
+        }
+    };
+}
+
+#[macro_export]
 macro_rules! impl_sha_serialize {
     ($fn_name:ident, $sha_ty:ty, $state_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(all(feature = "streaming", feature = "serialize"))]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(
             result_ptr: *mut u8,
@@ -162,12 +239,12 @@ macro_rules! impl_sha_serialize {
     };
 }
 
+#[macro_export]
 macro_rules! impl_sha_deserialize {
     ($fn_name:ident, $sha_ty:ty, $state_ty:ty) => {
         #[doc = "# Safety"]
         #[doc = ""]
         #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
-        #[cfg(all(feature = "streaming", feature = "deserialize"))]
         #[unsafe(no_mangle)]
         pub unsafe extern "C" fn $fn_name(
             ctx: *mut $sha_ty,
@@ -195,6 +272,64 @@ macro_rules! impl_sha_deserialize {
     };
 }
 
+#[macro_export]
+macro_rules! impl_sha_serialize_compact {
+    ($fn_name:ident, $sha_ty:ty, $state_ty:ty) => {
+        #[doc = "# Safety"]
+        #[doc = ""]
+        #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $fn_name(
+            result_ptr: *mut u8,
+            result_size: usize,
+            ctx: *const $sha_ty,
+        ) -> usize {
+            if result_ptr.is_null() {
+                return <$state_ty>::COMPACT_MAX_SIZE;
+            }
+
+            let ctx = unsafe { &*ctx };
+            let result_buf = unsafe { ::core::slice::from_raw_parts_mut(result_ptr, result_size) };
+            <$state_ty>::from(ctx)
+                .serialize_compact(result_buf)
+                .unwrap_or(0)
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_sha_deserialize_compact {
+    ($fn_name:ident, $sha_ty:ty, $state_ty:ty) => {
+        #[doc = "# Safety"]
+        #[doc = ""]
+        #[doc = "C bindings. Caller is responsible for ensuring memory correctness."]
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $fn_name(
+            ctx: *mut $sha_ty,
+            ctx_size: usize,
+            state: *const u8,
+            state_size: usize,
+        ) -> usize {
+            let size = ::core::mem::size_of::<$sha_ty>();
+
+            if !ctx.is_null() {
+                if state.is_null() || ctx_size < size {
+                    return 0;
+                }
+
+                let state = unsafe { ::core::slice::from_raw_parts(state, state_size) };
+                let ctx = unsafe { &mut *ctx };
+                match <$state_ty>::deserialize_compact(state).and_then(|s| <$sha_ty>::try_from(s)) {
+                    Ok(result) => *ctx = result,
+                    Err(_) => return 0,
+                }
+            }
+
+            size
+        }
+    };
+}
+
 #[cfg(feature = "sha224")]
 mod sha224;
 #[cfg(feature = "sha256")]
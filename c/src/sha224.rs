@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha224;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha224Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha224State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha224Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha224_init, Sha224);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha224_reset, Sha224);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha224_update, Sha224);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha224_finalize, Sha224, Sha224Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha224_digest, Sha224, Sha224Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha224_digest_batch, Sha224, Sha224Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha224_serialize, Sha224, Sha224State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha224_deserialize, Sha224, Sha224State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha224_serialize_compact, Sha224, Sha224State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha224_deserialize_compact, Sha224, Sha224State);
@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha256;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha256Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha256State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha256Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha256_init, Sha256);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha256_reset, Sha256);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha256_update, Sha256);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha256_finalize, Sha256, Sha256Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha256_digest, Sha256, Sha256Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha256_digest_batch, Sha256, Sha256Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha256_serialize, Sha256, Sha256State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha256_deserialize, Sha256, Sha256State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha256_serialize_compact, Sha256, Sha256State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha256_deserialize_compact, Sha256, Sha256State);
@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha384;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha384Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha384State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha384Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha384_init, Sha384);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha384_reset, Sha384);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha384_update, Sha384);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha384_finalize, Sha384, Sha384Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha384_digest, Sha384, Sha384Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha384_digest_batch, Sha384, Sha384Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha384_serialize, Sha384, Sha384State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha384_deserialize, Sha384, Sha384State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha384_serialize_compact, Sha384, Sha384State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha384_deserialize_compact, Sha384, Sha384State);
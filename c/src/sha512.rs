@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha512;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha512Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha512State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha512Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha512_init, Sha512);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha512_reset, Sha512);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha512_update, Sha512);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha512_finalize, Sha512, Sha512Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha512_digest, Sha512, Sha512Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha512_digest_batch, Sha512, Sha512Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha512_serialize, Sha512, Sha512State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha512_deserialize, Sha512, Sha512State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha512_serialize_compact, Sha512, Sha512State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha512_deserialize_compact, Sha512, Sha512State);
@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha512_224;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha512_224Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha512_224State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha512_224Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha512_224_init, Sha512_224);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha512_224_reset, Sha512_224);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha512_224_update, Sha512_224);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha512_224_finalize, Sha512_224, Sha512_224Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha512_224_digest, Sha512_224, Sha512_224Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha512_224_digest_batch, Sha512_224, Sha512_224Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha512_224_serialize, Sha512_224, Sha512_224State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha512_224_deserialize, Sha512_224, Sha512_224State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha512_224_serialize_compact, Sha512_224, Sha512_224State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha512_224_deserialize_compact, Sha512_224, Sha512_224State);
@@ -15,18 +15,31 @@
 
 #[cfg(any(feature = "streaming", feature = "sync"))]
 use ::sha2::Sha512_256;
+#[cfg(any(feature = "streaming", feature = "sync"))]
+use ::sha2::Sha512_256Cfg;
 #[cfg(all(
     feature = "streaming",
     any(feature = "deserialize", feature = "serialize")
 ))]
 use ::sha2::Sha512_256State;
-#[cfg(any(feature = "streaming", feature = "sync"))]
-use ::sha2::{Sha512_256Cfg, ShaVariant};
 
+#[cfg(feature = "streaming")]
 impl_sha_init!(sha512_256_init, Sha512_256);
+#[cfg(feature = "streaming")]
 impl_sha_reset!(sha512_256_reset, Sha512_256);
+#[cfg(feature = "streaming")]
 impl_sha_update!(sha512_256_update, Sha512_256);
+#[cfg(feature = "streaming")]
 impl_sha_finalize!(sha512_256_finalize, Sha512_256, Sha512_256Cfg);
+#[cfg(feature = "sync")]
 impl_sha_digest!(sha512_256_digest, Sha512_256, Sha512_256Cfg);
+#[cfg(feature = "sync")]
+impl_sha_digest_batch!(sha512_256_digest_batch, Sha512_256, Sha512_256Cfg);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
 impl_sha_serialize!(sha512_256_serialize, Sha512_256, Sha512_256State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
 impl_sha_deserialize!(sha512_256_deserialize, Sha512_256, Sha512_256State);
+#[cfg(all(feature = "streaming", feature = "serialize"))]
+impl_sha_serialize_compact!(sha512_256_serialize_compact, Sha512_256, Sha512_256State);
+#[cfg(all(feature = "streaming", feature = "deserialize"))]
+impl_sha_deserialize_compact!(sha512_256_deserialize_compact, Sha512_256, Sha512_256State);
@@ -38,9 +38,18 @@ async function buildVariant(featureSet: IFeatureSet): Promise<void> {
 	clonedFeatureSet.features.push('sha2-compress-unrolled');
 	clonedFeatureSet.slug = clonedFeatureSet.slug + '+sha2-compress-unrolled';
 
+	// Same as the unrolled build, plus SIMD128; only for engines with SIMD.
+	const simdFeatureSet = structuredClone(clonedFeatureSet);
+	simdFeatureSet.features.push('simd128');
+	simdFeatureSet.slug = simdFeatureSet.slug + '+simd128';
+
 	const wasmPathUnrolled = await buildCargo(clonedFeatureSet, {
 		bulkMemory: false,
 		multivalue: true,
+	});
+	const wasmPathSimd = await buildCargo(simdFeatureSet, {
+		bulkMemory: false,
+		multivalue: true,
 		simd128: true,
 	});
 	const wasmPathCompact = await buildCargo(featureSet);
@@ -49,6 +58,10 @@ async function buildVariant(featureSet: IFeatureSet): Promise<void> {
 		clonedFeatureSet,
 		wasmPathUnrolled,
 	);
+	const wasmPathSimdOptimized = await optimizeWasm(
+		simdFeatureSet,
+		wasmPathSimd,
+	);
 	const wasmPathCompactOptimized = await optimizeWasm(
 		featureSet,
 		wasmPathCompact,
@@ -60,6 +73,11 @@ async function buildVariant(featureSet: IFeatureSet): Promise<void> {
 		featureSet,
 		wasmPathUnrolledOptimized,
 	);
+	const wrappedPathWasmSimd = await bundleWrapperWasm(
+		featureSet,
+		wasmPathSimdOptimized,
+		'wasm-simd',
+	);
 
 	console.log('  Converting WASM → JS …');
 	const { wasmJsPath } = await convertWasmToJs(
@@ -76,8 +94,10 @@ async function buildVariant(featureSet: IFeatureSet): Promise<void> {
 	console.log('  Building CJS + ESM outputs …');
 	await Promise.all([
 		buildCjs(featureSet, wrappedPathWasm, 'wasm'),
+		buildCjs(featureSet, wrappedPathWasmSimd, 'wasm-simd'),
 		buildCjs(featureSet, wrappedPathEcmascript, 'es'),
 		buildEsm(featureSet, wrappedPathWasm, 'wasm'),
+		buildEsm(featureSet, wrappedPathWasmSimd, 'wasm-simd'),
 		buildEsm(featureSet, wrappedPathEcmascript, 'es'),
 	]);
 
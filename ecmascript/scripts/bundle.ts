@@ -80,6 +80,7 @@ const wasmPathFormatter = (p: string) => {
  *
  * @param featureSet - Object containing feature flags to include in the build.
  * @param wasmPath - Path to the original wasm file (not wasm2js).
+ * @param type - Output type, used in the file name (`wasm` or `wasm-simd`).
  * @returns Path to the bundled + formatted output file containing embedded wasm.
  * @example
  * const output = await bundleWrapperWasm(
@@ -90,8 +91,12 @@ const wasmPathFormatter = (p: string) => {
 export async function bundleWrapperWasm(
 	featureSet: IFeatureSet,
 	wasmPath: string,
+	type = 'wasm',
 ): Promise<string> {
-	const outfile = join(BUILD_DIR, `${featureSet.slug}.wrapped.wasm.js`);
+	const outfile = join(
+		BUILD_DIR,
+		`${featureSet.slug}.wrapped.${type}.js`,
+	);
 
 	return bundleWrapperFactory(outfile, [
 		{
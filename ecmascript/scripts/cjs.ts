@@ -69,7 +69,7 @@ export async function buildCjs(
 	await writeFile(intermediaryPath, wrapped, 'utf-8');
 
 	let externsFiles: string[] | undefined;
-	if (type === 'wasm') {
+	if (type === 'wasm' || type === 'wasm-simd') {
 		externsFiles = [join(RESOURCES_DIR, 'closure.externs.wasm.js')];
 	}
 	await runClosureCompiler(intermediaryPath, outputPath, externsFiles);
@@ -60,4 +60,5 @@ export type Feature =
 	| 'sha512_256'
 	| 'deserialize'
 	| 'serialize'
+	| 'simd128'
 	| 'streaming';
@@ -50,7 +50,7 @@ export async function buildEsm(
 
 	/* ---- minify ---- */
 	let externsFiles: string[] | undefined;
-	if (type === 'wasm') {
+	if (type === 'wasm' || type === 'wasm-simd') {
 		externsFiles = [join(RESOURCES_DIR, 'closure.externs.wasm.js')];
 	}
 	await runClosureCompiler(
@@ -89,11 +89,13 @@ export async function generatePackageJson(
 	// Bare "." → most-featured variant
 	exports['.'] = makeConditionEntry(primary, 'es');
 	exports['./wasm'] = makeConditionEntry(primary, 'wasm');
+	exports['./wasm-simd'] = makeConditionEntry(primary, 'wasm-simd');
 
 	// Named sub-paths for every variant (including the primary)
 	for (const fs of featureSets) {
 		exports[`./${fs.slug}`] = makeConditionEntry(fs, 'es');
 		exports[`./wasm/${fs.slug}`] = makeConditionEntry(fs, 'wasm');
+		exports[`./wasm-simd/${fs.slug}`] = makeConditionEntry(fs, 'wasm-simd');
 	}
 
 	template.exports = exports;
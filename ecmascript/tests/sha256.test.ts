@@ -21,12 +21,16 @@ const sha2Esm = await getSha2Instance();
 const sha2Cjs = await getSha2Instance(undefined, true);
 const sha2WasmEsm = await getSha2Instance('wasm', false);
 const sha2WasmCjs = await getSha2Instance('wasm', false);
+const sha2WasmSimdEsm = await getSha2Instance('wasm-simd', false);
+const sha2WasmSimdCjs = await getSha2Instance('wasm-simd', true);
 
 const skip = !sha2Esm.sha256;
 const getEsmInstance = () => sha2Esm.sha256();
 const getCjsInstance = () => sha2Cjs.sha256();
 const getWasmEsmInstance = () => sha2WasmEsm.sha256();
 const getWasmCjsInstance = () => sha2WasmCjs.sha256();
+const getWasmSimdEsmInstance = () => sha2WasmSimdEsm.sha256();
+const getWasmSimdCjsInstance = () => sha2WasmSimdCjs.sha256();
 
 const shortMsgVectors = loadVectors('SHA256ShortMsg.rsp');
 const longMsgVectors = loadVectors('SHA256LongMsg.rsp');
@@ -90,3 +94,33 @@ testsFactory(
 	longMsgVectors,
 	skip,
 );
+
+testsFactory(
+	'SHA-256 (WASM-SIMD+ESM)',
+	getWasmSimdEsmInstance,
+	'NIST ShortMsg',
+	shortMsgVectors,
+	skip,
+);
+testsFactory(
+	'SHA-256 (WASM-SIMD+ESM)',
+	getWasmSimdEsmInstance,
+	'NIST LongMsg',
+	longMsgVectors,
+	skip,
+);
+
+testsFactory(
+	'SHA-256 (WASM-SIMD+CJS)',
+	getWasmSimdCjsInstance,
+	'NIST ShortMsg',
+	shortMsgVectors,
+	skip,
+);
+testsFactory(
+	'SHA-256 (WASM-SIMD+CJS)',
+	getWasmSimdCjsInstance,
+	'NIST LongMsg',
+	longMsgVectors,
+	skip,
+);
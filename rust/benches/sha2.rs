@@ -18,6 +18,8 @@ use ::criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion
 
 const SIZES: [usize; 4] = [32, 1024, 64 * 1024, 1024 * 1024];
 const STREAMING_CHUNK_SIZE: usize = 1024;
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+const COMPRESS_BLOCKS: usize = 1024;
 
 fn bench_hasher<const DIGEST: usize, H, F>(c: &mut Criterion, group_name: &str, mut new_hasher: F)
 where
@@ -156,8 +158,97 @@ fn bench_sha512_256(c: &mut Criterion) {
 #[cfg(not(feature = "sha512_256"))]
 fn bench_sha512_256(_: &mut Criterion) {}
 
+/// Raw compression over a run of blocks, without buffering or padding,
+/// once per backend the family supports on this machine.
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+fn bench_compress<F: sha2::ShaFamily, const BLOCK: usize>(
+    c: &mut Criterion,
+    group_name: &str,
+    iv: [F::Word; 8],
+    compress: fn(&mut [F::Word; 8], &[u8; BLOCK]),
+) {
+    let blocks = vec![[0xA5; BLOCK]; COMPRESS_BLOCKS];
+    let mut group = c.benchmark_group(group_name);
+    group.throughput(Throughput::Bytes((COMPRESS_BLOCKS * BLOCK) as u64));
+
+    for backend in sha2::Backend::ALL {
+        if F::force_backend(Some(backend)).is_err() {
+            continue;
+        }
+        group.bench_function(backend.name(), |b| {
+            b.iter(|| {
+                let mut state = iv;
+                for block in black_box(&blocks) {
+                    compress(&mut state, block);
+                }
+                black_box(state);
+            });
+        });
+    }
+    F::force_backend(None).unwrap();
+
+    group.finish();
+}
+
+#[cfg(feature = "sha256")]
+fn bench_compress256(c: &mut Criterion) {
+    bench_compress::<sha2::Sha2_32, 64>(
+        c,
+        "compress256",
+        sha2::raw::SHA256_IV,
+        sha2::raw::compress256,
+    );
+}
+
+#[cfg(not(feature = "sha256"))]
+fn bench_compress256(_: &mut Criterion) {}
+
+#[cfg(feature = "sha512")]
+fn bench_compress512(c: &mut Criterion) {
+    bench_compress::<sha2::Sha2_64, 128>(
+        c,
+        "compress512",
+        sha2::raw::SHA512_IV,
+        sha2::raw::compress512,
+    );
+}
+
+#[cfg(not(feature = "sha512"))]
+fn bench_compress512(_: &mut Criterion) {}
+
+/// Fixed-length 32- and 64-byte messages against the general one-shot path.
+#[cfg(all(feature = "sha256", feature = "sync"))]
+fn bench_fixed256(c: &mut Criterion) {
+    let data = [0xA5u8; 64];
+    let mut group = c.benchmark_group("sha256_fixed");
+
+    group.throughput(Throughput::Bytes(32));
+    group.bench_function(BenchmarkId::new("digest", 32), |b| {
+        b.iter(|| sha2::Sha256::digest(black_box(&data[..32])));
+    });
+    group.bench_function(BenchmarkId::new("sha256_32bytes", 32), |b| {
+        b.iter(|| sha2::sha256_32bytes(black_box(data.first_chunk().unwrap())));
+    });
+
+    group.throughput(Throughput::Bytes(64));
+    group.bench_function(BenchmarkId::new("digest", 64), |b| {
+        b.iter(|| sha2::Sha256::digest(black_box(&data)));
+    });
+    group.bench_function(BenchmarkId::new("sha256_64bytes", 64), |b| {
+        b.iter(|| sha2::sha256_64bytes(black_box(&data)));
+    });
+
+    group.finish();
+}
+
+#[cfg(not(all(feature = "sha256", feature = "sync")))]
+fn bench_fixed256(_: &mut Criterion) {}
+
 criterion_group!(
     benches,
+    bench_compress256,
+    bench_compress512,
+    bench_fixed256,
     bench_sha224,
     bench_sha256,
     bench_sha384,
@@ -28,7 +28,37 @@ mod sha2_internals;
     feature = "sha512_224",
     feature = "sha512_256"
 ))]
-pub use crate::sha2_internals::ShaVariant;
+pub use crate::sha2_internals::{
+    Backend, HashDrbg, Hkdf, Hmac, HmacDrbg, ShaFamily, ShaHasher, ShaVariant, ShaWord, drbg,
+    hash_to_curve, kdf, raw, rfc6979, rsa_encoding, tls,
+};
+#[cfg(all(
+    feature = "std",
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+pub use crate::sha2_internals::{TreeHasher, TreeParams};
+
+#[cfg(all(feature = "std", any(feature = "sha256", feature = "sha512")))]
+pub use crate::sha2_internals::scram;
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+pub use crate::sha2_internals::{noise, otp, sha_crypt};
+
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+pub use crate::sha2_internals::Sha2_32;
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub use crate::sha2_internals::Sha2_64;
 
 #[cfg(any(feature = "serialize", feature = "deserialize"))]
 pub use crate::sha2_internals::ShaState;
@@ -38,45 +68,72 @@ pub use crate::sha2_internals::ShaState;
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha224State;
+#[cfg(all(feature = "std", feature = "sha224"))]
+pub use crate::sha2_internals::Sha224Tree;
 #[cfg(feature = "sha224")]
-pub use crate::sha2_internals::{Sha224, Sha224Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha224, HkdfSha224, HmacDrbgSha224, HmacSha224, Sha224, Sha224Cfg,
+};
 
 #[cfg(all(
     feature = "sha256",
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha256State;
+#[cfg(all(feature = "std", feature = "sha256"))]
+pub use crate::sha2_internals::Sha256Tree;
 #[cfg(feature = "sha256")]
-pub use crate::sha2_internals::{Sha256, Sha256Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha256, HkdfSha256, HmacDrbgSha256, HmacSha256, Sha256, Sha256Cfg, Sha256x8,
+    sha256_32bytes, sha256_64bytes,
+};
 
 #[cfg(all(
     feature = "sha384",
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha384State;
+#[cfg(all(feature = "std", feature = "sha384"))]
+pub use crate::sha2_internals::Sha384Tree;
 #[cfg(feature = "sha384")]
-pub use crate::sha2_internals::{Sha384, Sha384Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha384, HkdfSha384, HmacDrbgSha384, HmacSha384, Sha384, Sha384Cfg,
+};
 
 #[cfg(all(
     feature = "sha512",
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha512State;
+#[cfg(all(feature = "std", feature = "sha512"))]
+pub use crate::sha2_internals::Sha512Tree;
 #[cfg(feature = "sha512")]
-pub use crate::sha2_internals::{Sha512, Sha512Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha512, HkdfSha512, HmacDrbgSha512, HmacSha512, Sha512, Sha512Cfg,
+};
 
 #[cfg(all(
     feature = "sha512_224",
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha512_224State;
+#[cfg(all(feature = "std", feature = "sha512_224"))]
+pub use crate::sha2_internals::Sha512_224Tree;
 #[cfg(feature = "sha512_224")]
-pub use crate::sha2_internals::{Sha512_224, Sha512_224Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha512_224, HkdfSha512_224, HmacDrbgSha512_224, HmacSha512_224, Sha512_224,
+    Sha512_224Cfg,
+};
 
 #[cfg(all(
     feature = "sha512_256",
     any(feature = "serialize", feature = "deserialize")
 ))]
 pub use crate::sha2_internals::Sha512_256State;
+#[cfg(all(feature = "std", feature = "sha512_256"))]
+pub use crate::sha2_internals::Sha512_256Tree;
 #[cfg(feature = "sha512_256")]
-pub use crate::sha2_internals::{Sha512_256, Sha512_256Cfg};
+pub use crate::sha2_internals::{
+    HashDrbgSha512_256, HkdfSha512_256, HmacDrbgSha512_256, HmacSha512_256, Sha512_256,
+    Sha512_256Cfg,
+};
@@ -0,0 +1,115 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+//! Compression backend selection.
+//!
+//! Each family picks its best available backend on first use and caches
+//! the choice in an atomic; with `std` the choice reflects runtime CPU
+//! detection, otherwise only features enabled at compile time count.
+//! [`ShaFamily::force_backend`] overrides the choice, e.g. to test every
+//! backend on one machine.
+//!
+//! [`ShaFamily::force_backend`]: super::ShaFamily::force_backend
+
+use ::core::{
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// A single-stream compression implementation.
+///
+/// Which backends a family can use is reported by
+/// [`ShaFamily::supports_backend`](super::ShaFamily::supports_backend).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Scalar rounds in a loop; always available.
+    Portable,
+    /// Fully unrolled scalar rounds; needs the `sha2-compress-unrolled`
+    /// feature.
+    PortableUnrolled,
+    /// x86 SHA extensions; SHA-224/256 only.
+    ShaNi,
+    /// x86 AVX2 message schedule interleaved with unrolled BMI2 rounds;
+    /// SHA-384/512 family only.
+    Avx2,
+    /// WebAssembly SIMD128 message schedule; SHA-224/256 only, and only
+    /// when built with the `simd128` feature for a target that enables it.
+    Simd128,
+}
+
+impl Backend {
+    /// Every backend, in declaration order.
+    pub const ALL: [Backend; 5] = [
+        Backend::Portable,
+        Backend::PortableUnrolled,
+        Backend::ShaNi,
+        Backend::Avx2,
+        Backend::Simd128,
+    ];
+
+    /// Short stable identifier, suitable for logs and telemetry.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Backend::Portable => "portable",
+            Backend::PortableUnrolled => "portable-unrolled",
+            Backend::ShaNi => "sha-ni",
+            Backend::Avx2 => "avx2",
+            Backend::Simd128 => "simd128",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Cached backend of one family; `0` means not yet selected.
+pub(super) struct Slot(AtomicU8);
+
+impl Slot {
+    pub(super) const fn new() -> Self {
+        Self(AtomicU8::new(0))
+    }
+
+    /// The cached backend, selecting it with `detect` on first use.
+    #[inline(always)]
+    pub(super) fn get(&self, detect: impl FnOnce() -> Backend) -> Backend {
+        match self.0.load(Ordering::Relaxed) {
+            0 => {
+                let backend = detect();
+                self.set(Some(backend));
+                backend
+            }
+            bits => Backend::ALL[usize::from(bits) - 1],
+        }
+    }
+
+    /// Pin `backend`, or go back to detection with `None`.
+    pub(super) fn set(&self, backend: Option<Backend>) {
+        let bits = backend.map_or(0, |b| b as u8 + 1);
+        self.0.store(bits, Ordering::Relaxed);
+    }
+}
+
+/// The first backend in `preference` that `supported` accepts.
+pub(super) fn select(preference: &[Backend], supported: fn(Backend) -> bool) -> Backend {
+    preference
+        .iter()
+        .copied()
+        .find(|&b| supported(b))
+        .unwrap_or(Backend::Portable)
+}
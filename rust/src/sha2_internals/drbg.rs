@@ -0,0 +1,307 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Deterministic random bit generators from NIST SP 800-90A Rev. 1:
+//! HMAC_DRBG and Hash_DRBG over any SHA-2 variant.
+//!
+//! Neither generator gathers entropy itself. The caller passes entropy
+//! input at instantiation, on every reseed and on every request with
+//! prediction resistance, so the same inputs always give the same output.
+
+use super::{hasher::ShaHasher, hmac::Hmac, variant::ShaVariant};
+
+/// Generate requests allowed between reseeds (SP 800-90A, table 2).
+pub const RESEED_INTERVAL: u64 = 1 << 48;
+/// Largest single generate request in bytes (2^19 bits).
+pub const MAX_REQUEST_BYTES: usize = 1 << 16;
+
+/// Security strength in bytes of a hash with `digest_bytes` of output
+/// (SP 800-57 part 1, table 3), which is also the minimum entropy input.
+const fn security_strength(digest_bytes: usize) -> usize {
+    if digest_bytes >= 32 {
+        32
+    } else if digest_bytes >= 28 {
+        24
+    } else {
+        digest_bytes / 2
+    }
+}
+
+/// Checks shared by both generators before a generate request.
+fn check_request(len: usize, reseed_counter: u64, interval: u64) -> Result<(), &'static str> {
+    if len > MAX_REQUEST_BYTES {
+        return Err("request too large");
+    }
+    if reseed_counter > interval {
+        return Err("reseed required");
+    }
+    Ok(())
+}
+
+fn check_entropy(entropy: &[u8], digest_bytes: usize) -> Result<(), &'static str> {
+    if entropy.len() < security_strength(digest_bytes) {
+        return Err("entropy input too short");
+    }
+    Ok(())
+}
+
+// ---- HMAC_DRBG -------------------------------------------------------------
+
+/// HMAC_DRBG (SP 800-90A section 10.1.2).
+pub struct HmacDrbg<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    k: [u8; DIGEST],
+    v: [u8; DIGEST],
+    reseed_counter: u64,
+    reseed_interval: u64,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> HmacDrbg<V, B, D> {
+    /// Instantiate from entropy input, a nonce and an optional
+    /// personalisation string.
+    ///
+    /// Fails if `entropy` is shorter than the security strength of `V`.
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, &'static str> {
+        check_entropy(entropy, D)?;
+        let mut drbg = Self {
+            k: [0x00; D],
+            v: [0x01; D],
+            reseed_counter: 1,
+            reseed_interval: RESEED_INTERVAL,
+            _variant: ::core::marker::PhantomData,
+        };
+        drbg.update(&[entropy, nonce, personalization]);
+        Ok(drbg)
+    }
+
+    /// Mix in fresh entropy and optional additional input.
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), &'static str> {
+        check_entropy(entropy, D)?;
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// Fill `out` with pseudorandom bytes, mixing in optional additional
+    /// input.
+    ///
+    /// Fails once the reseed interval is exhausted or if `out` is longer
+    /// than [`MAX_REQUEST_BYTES`].
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str> {
+        check_request(out.len(), self.reseed_counter, self.reseed_interval)?;
+
+        if !additional.is_empty() {
+            self.update(&[additional]);
+        }
+        for chunk in out.chunks_mut(D) {
+            self.v = Hmac::<V, B, D>::mac(&self.k, &self.v);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// [`generate`](Self::generate) with prediction resistance: reseed from
+    /// `entropy` and `additional` first, then generate without additional
+    /// input.
+    pub fn generate_with_prediction_resistance(
+        &mut self,
+        entropy: &[u8],
+        out: &mut [u8],
+        additional: &[u8],
+    ) -> Result<(), &'static str> {
+        check_request(out.len(), 0, self.reseed_interval)?;
+        self.reseed(entropy, additional)?;
+        self.generate(out, &[])
+    }
+
+    /// Generate requests made since the last (re)seed, plus one.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    /// Require a reseed after `interval` generate requests, at most
+    /// [`RESEED_INTERVAL`].
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        self.reseed_interval = interval.clamp(1, RESEED_INTERVAL);
+    }
+
+    /// HMAC_DRBG_Update over the concatenation of `provided`.
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|p| p.is_empty());
+        for sep in [0x00u8, 0x01] {
+            let mut mac = Hmac::<V, B, D>::new(&self.k);
+            mac.update(&self.v);
+            mac.update(&[sep]);
+            for p in provided {
+                mac.update(p);
+            }
+            self.k = mac.finalize();
+            self.v = Hmac::<V, B, D>::mac(&self.k, &self.v);
+            if empty {
+                break;
+            }
+        }
+    }
+}
+
+// ---- Hash_DRBG -------------------------------------------------------------
+
+/// Longest seed of any variant: 888 bits.
+const MAX_SEED_BYTES: usize = 111;
+
+/// Hash_DRBG (SP 800-90A section 10.1.1).
+pub struct HashDrbg<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    v: [u8; MAX_SEED_BYTES],
+    c: [u8; MAX_SEED_BYTES],
+    reseed_counter: u64,
+    reseed_interval: u64,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> HashDrbg<V, B, D> {
+    /// Seed length in bytes: 440 bits for digests up to 256 bits, else
+    /// 888 bits (SP 800-90A, table 2).
+    pub const SEED_BYTES: usize = if D <= 32 { 55 } else { MAX_SEED_BYTES };
+
+    /// Instantiate from entropy input, a nonce and an optional
+    /// personalisation string.
+    ///
+    /// Fails if `entropy` is shorter than the security strength of `V`.
+    pub fn new(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, &'static str> {
+        check_entropy(entropy, D)?;
+        let mut drbg = Self {
+            v: [0; MAX_SEED_BYTES],
+            c: [0; MAX_SEED_BYTES],
+            reseed_counter: 1,
+            reseed_interval: RESEED_INTERVAL,
+            _variant: ::core::marker::PhantomData,
+        };
+        let mut v = [0u8; MAX_SEED_BYTES];
+        Self::hash_df(&[entropy, nonce, personalization], &mut v);
+        drbg.set_v(&v);
+        Ok(drbg)
+    }
+
+    /// Mix in fresh entropy and optional additional input.
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), &'static str> {
+        check_entropy(entropy, D)?;
+        let mut v = [0u8; MAX_SEED_BYTES];
+        Self::hash_df(&[&[0x01], self.seed(), entropy, additional], &mut v);
+        self.set_v(&v);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// Fill `out` with pseudorandom bytes, mixing in optional additional
+    /// input.
+    ///
+    /// Fails once the reseed interval is exhausted or if `out` is longer
+    /// than [`MAX_REQUEST_BYTES`].
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), &'static str> {
+        check_request(out.len(), self.reseed_counter, self.reseed_interval)?;
+        let n = Self::SEED_BYTES;
+
+        if !additional.is_empty() {
+            let w = Self::hash(&[&[0x02], self.seed(), additional]);
+            add_be(&mut self.v[..n], &w);
+        }
+
+        // Hashgen
+        let mut data = self.v;
+        for chunk in out.chunks_mut(D) {
+            chunk.copy_from_slice(&Self::hash(&[&data[..n]])[..chunk.len()]);
+            add_be(&mut data[..n], &[1]);
+        }
+
+        let h = Self::hash(&[&[0x03], self.seed()]);
+        let c = self.c;
+        add_be(&mut self.v[..n], &h);
+        add_be(&mut self.v[..n], &c[..n]);
+        add_be(&mut self.v[..n], &self.reseed_counter.to_be_bytes());
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    /// [`generate`](Self::generate) with prediction resistance: reseed from
+    /// `entropy` and `additional` first, then generate without additional
+    /// input.
+    pub fn generate_with_prediction_resistance(
+        &mut self,
+        entropy: &[u8],
+        out: &mut [u8],
+        additional: &[u8],
+    ) -> Result<(), &'static str> {
+        check_request(out.len(), 0, self.reseed_interval)?;
+        self.reseed(entropy, additional)?;
+        self.generate(out, &[])
+    }
+
+    /// Generate requests made since the last (re)seed, plus one.
+    pub fn reseed_counter(&self) -> u64 {
+        self.reseed_counter
+    }
+
+    /// Require a reseed after `interval` generate requests, at most
+    /// [`RESEED_INTERVAL`].
+    pub fn set_reseed_interval(&mut self, interval: u64) {
+        self.reseed_interval = interval.clamp(1, RESEED_INTERVAL);
+    }
+
+    fn seed(&self) -> &[u8] {
+        &self.v[..Self::SEED_BYTES]
+    }
+
+    /// Install a new `V` and derive `C` from it.
+    fn set_v(&mut self, v: &[u8; MAX_SEED_BYTES]) {
+        self.v = *v;
+        Self::hash_df(&[&[0x00], &v[..Self::SEED_BYTES]], &mut self.c);
+    }
+
+    fn hash(parts: &[&[u8]]) -> [u8; D] {
+        let mut h = ShaHasher::<V, B, D>::new();
+        for p in parts {
+            h.update(p);
+        }
+        h.finalize()
+    }
+
+    /// Hash_df over the concatenation of `parts`, filling the first
+    /// [`SEED_BYTES`](Self::SEED_BYTES) of `out`.
+    fn hash_df(parts: &[&[u8]], out: &mut [u8; MAX_SEED_BYTES]) {
+        let bits = (Self::SEED_BYTES as u32 * 8).to_be_bytes();
+        for (counter, chunk) in (1u8..).zip(out[..Self::SEED_BYTES].chunks_mut(D)) {
+            let mut h = ShaHasher::<V, B, D>::new();
+            h.update(&[counter]);
+            h.update(&bits);
+            for p in parts {
+                h.update(p);
+            }
+            chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+        }
+    }
+}
+
+/// `dst = (dst + src) mod 2^(8 * dst.len())`, both big-endian.
+fn add_be(dst: &mut [u8], src: &[u8]) {
+    let mut carry = 0u16;
+    let mut src = src.iter().rev();
+    for d in dst.iter_mut().rev() {
+        let sum = *d as u16 + *src.next().unwrap_or(&0) as u16 + carry;
+        *d = sum as u8;
+        carry = sum >> 8;
+    }
+}
@@ -36,6 +36,20 @@ pub struct ShaEngine<F: ShaFamily> {
     pub(crate) words: [F::Word; 8],
 }
 
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+impl<F: ShaFamily> Clone for ShaEngine<F> {
+    fn clone(&self) -> Self {
+        Self { words: self.words }
+    }
+}
+
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -50,7 +64,7 @@ impl<F: ShaFamily> ShaEngine<F> {
     }
 
     #[inline(always)]
-    pub fn compress(&mut self, block: &[u8]) {
+    pub fn compress(&mut self, block: &F::Block) {
         F::compress(&mut self.words, block);
     }
 
@@ -62,7 +76,6 @@ impl<F: ShaFamily> ShaEngine<F> {
     }
 
     /// Deserialize eight big-endian words from `src`.
-    #[cfg(feature = "deserialize")]
     pub fn import(src: &[u8]) -> [F::Word; 8] {
         let mut words = [F::Word::ZERO; 8];
         let mut i = 0;
@@ -21,7 +21,11 @@
     feature = "sha512_224",
     feature = "sha512_256"
 ))]
-use super::word::ShaWord;
+use super::{
+    backend::{Backend, Slot, select},
+    sealed::Sealed,
+    word::ShaWord,
+};
 
 /// A SHA-2 compression-function family.
 ///
@@ -30,6 +34,11 @@ use super::word::ShaWord;
 ///
 /// The default [`compress`](ShaFamily::compress) is written once;
 /// families only supply sigma functions, round constants, and length encoding.
+///
+/// This trait is sealed: custom algorithms are declared as a [`ShaVariant`]
+/// over one of the two existing families.
+///
+/// [`ShaVariant`]: super::variant::ShaVariant
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -38,7 +47,7 @@ use super::word::ShaWord;
     feature = "sha512_224",
     feature = "sha512_256"
 ))]
-pub trait ShaFamily: 'static {
+pub trait ShaFamily: Sealed + 'static {
     type Word: ShaWord;
 
     /// Block size in bytes (64 or 128).
@@ -49,9 +58,25 @@ pub trait ShaFamily: 'static {
     const LEN_BYTES: usize;
     /// Pre-computed `8 × Word::BYTES` for convenience.
     const STATE_BYTES: usize;
+    /// Serialised checkpoint size: `STATE_BYTES + BLOCK_BYTES + 1 + LEN_BYTES`.
+    const SERIAL_BYTES: usize = Self::STATE_BYTES + Self::BLOCK_BYTES + 1 + Self::LEN_BYTES;
     /// Round constants.
     const K: &'static [Self::Word];
 
+    /// One input block, `[u8; BLOCK_BYTES]`.
+    ///
+    /// Taking whole blocks makes the word loads in the compression
+    /// functions free of bounds checks by construction. It is not a speed
+    /// change: on x86-64 and wasm32 the optimizer already removed those
+    /// checks from the slice-based code, and `compress256` throughput is
+    /// the same either way.
+    type Block;
+
+    /// View an `N`-byte array as a block; `N` must equal `BLOCK_BYTES`.
+    fn as_block<const N: usize>(bytes: &[u8; N]) -> &Self::Block;
+    /// Big-endian word `i` of `block`.
+    fn block_word(block: &Self::Block, i: usize) -> Self::Word;
+
     // ---- sigma functions (the only math that differs) ----
 
     fn small_sigma0(x: Self::Word) -> Self::Word;
@@ -68,14 +93,87 @@ pub trait ShaFamily: 'static {
     /// Deserialize byte count from a state checkpoint.
     fn read_len(src: &[u8]) -> u128;
 
-    /// Compress one block (`BLOCK_BYTES` bytes) into the 8-word chaining state.
+    /// Length of the final-block padding for a `total_bytes`-byte message.
+    #[inline(always)]
+    fn padding_len(total_bytes: u128) -> usize {
+        let used = (total_bytes % Self::BLOCK_BYTES as u128) as usize;
+        1 + (2 * Self::BLOCK_BYTES - used - 1 - Self::LEN_BYTES) % Self::BLOCK_BYTES
+            + Self::LEN_BYTES
+    }
+
+    /// Write the final-block padding (`0x80`, zeros, bit length) for a
+    /// `total_bytes`-byte message into `dst`, returning its length.
+    fn write_padding(total_bytes: u128, dst: &mut [u8]) -> usize {
+        let pad_len = Self::padding_len(total_bytes);
+        let len_at = pad_len - Self::LEN_BYTES;
+
+        dst[0] = 0x80;
+        dst[1..len_at].fill(0);
+        Self::encode_bit_len(total_bytes, &mut dst[len_at..pad_len]);
+        pad_len
+    }
+
+    // ---- backend selection ----
+
+    /// Whether `backend` can run this family on this build and CPU.
+    fn supports_backend(backend: Backend) -> bool;
+    /// The backend [`compress`](ShaFamily::compress) dispatches to,
+    /// selecting the best supported one on first use.
+    fn active_backend() -> Backend;
+    /// Pin [`compress`](ShaFamily::compress) to `backend`, or return to
+    /// automatic selection with `None`. Affects every thread; meant for
+    /// tests and benchmarks.
+    ///
+    /// Fails if the backend is not supported.
+    fn force_backend(backend: Option<Backend>) -> Result<(), &'static str>;
+
+    /// Hash independent messages from the chaining value `iv` on a
+    /// multi-message backend, keeping the first `D` bytes of each final
+    /// state. Returns `false`, leaving `outputs` untouched, if the family
+    /// has no such backend worth using here.
+    #[inline(always)]
+    fn hash_batch<const D: usize>(
+        iv: &[Self::Word; 8],
+        inputs: &[&[u8]],
+        outputs: &mut [[u8; D]],
+    ) -> bool {
+        let _ = (iv, inputs, outputs);
+        false
+    }
+
+    /// Compress one block into the 8-word chaining state, on the
+    /// [active backend](ShaFamily::active_backend).
+    #[inline(always)]
+    fn compress(state: &mut [Self::Word; 8], block: &Self::Block) {
+        Self::compress_portable(state, block);
+    }
+
+    /// Portable scalar compression: size-optimised with the
+    /// `sha2-compress-small` feature, else unrolled with the
+    /// `sha2-compress-unrolled` feature, else rolled.
+    #[inline(always)]
+    fn compress_portable(state: &mut [Self::Word; 8], block: &Self::Block) {
+        #[cfg(feature = "sha2-compress-small")]
+        Self::compress_small(state, block);
+        #[cfg(all(
+            feature = "sha2-compress-unrolled",
+            not(feature = "sha2-compress-small")
+        ))]
+        Self::compress_unrolled(state, block);
+        #[cfg(not(any(feature = "sha2-compress-small", feature = "sha2-compress-unrolled")))]
+        Self::compress_rolled(state, block);
+    }
+
+    /// Scalar compression with every round unrolled, written once for both
+    /// families.
     // unused_assignments false positive on `recalc_w` (`$w[idx] = wv;`)
     #[allow(unused_assignments)]
     #[inline(always)]
-    #[cfg(feature = "sha2-compress-unrolled")]
-    fn compress(state: &mut [Self::Word; 8], block: &[u8]) {
-        debug_assert!(block.len() >= Self::BLOCK_BYTES);
-
+    #[cfg(all(
+        feature = "sha2-compress-unrolled",
+        not(feature = "sha2-compress-small")
+    ))]
+    fn compress_unrolled(state: &mut [Self::Word; 8], block: &Self::Block) {
         macro_rules! round {
             ($a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $k:expr, $data:expr) => {{
                 let t1 = $h
@@ -92,7 +190,7 @@ pub trait ShaFamily: 'static {
 
         macro_rules! round_1_16 {
             ($w:ident, $a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $n:expr) => {{
-                let wv = Self::Word::from_be_bytes_at(block, $n);
+                let wv = Self::block_word(block, $n);
                 $w[$n] = wv;
                 round!($a, $b, $c, $d, $e, $f, $g, $h, Self::K[$n], wv);
             }};
@@ -188,15 +286,52 @@ pub trait ShaFamily: 'static {
         state[7] = state[7].wrapping_add(h);
     }
 
-    #[inline(always)]
-    #[cfg(not(feature = "sha2-compress-unrolled"))]
-    fn compress(state: &mut [Self::Word; 8], block: &[u8]) {
-        debug_assert!(block.len() >= Self::BLOCK_BYTES);
+    /// Scalar compression tuned for code size (`sha2-compress-small`).
+    ///
+    /// Kept out of line, so each family has exactly one copy shared by
+    /// every variant and call site. The rounds are driven by the `K` table
+    /// and rotate the working variables through an array; the schedule is
+    /// a 16-word ring, so the stack holds only `w`, the working variables
+    /// and the state.
+    #[inline(never)]
+    #[cfg(feature = "sha2-compress-small")]
+    fn compress_small(state: &mut [Self::Word; 8], block: &Self::Block) {
+        let mut w = [Self::Word::ZERO; 16];
+        let mut v = *state;
+
+        for (i, &k) in Self::K.iter().enumerate() {
+            let wi = if i < 16 {
+                Self::block_word(block, i)
+            } else {
+                w[i & 15]
+                    .wrapping_add(Self::small_sigma1(w[(i + 14) & 15]))
+                    .wrapping_add(w[(i + 9) & 15])
+                    .wrapping_add(Self::small_sigma0(w[(i + 1) & 15]))
+            };
+            w[i & 15] = wi;
+
+            let [a, b, c, d, e, f, g, h] = v;
+            let t1 = h
+                .wrapping_add(Self::big_sigma1(e))
+                .wrapping_add(g ^ (e & (f ^ g)))
+                .wrapping_add(k)
+                .wrapping_add(wi);
+            let t2 = Self::big_sigma0(a).wrapping_add((a & b) ^ (c & (a ^ b)));
+            v = [t1.wrapping_add(t2), a, b, c, d.wrapping_add(t1), e, f, g];
+        }
 
+        for (s, x) in state.iter_mut().zip(v) {
+            *s = s.wrapping_add(x);
+        }
+    }
+
+    /// Scalar compression as a single loop over the rounds.
+    #[inline(always)]
+    fn compress_rolled(state: &mut [Self::Word; 8], block: &Self::Block) {
         let mut w = [Self::Word::ZERO; 16];
         let mut i = 0;
         while i < 16 {
-            w[i] = Self::Word::from_be_bytes_at(block, i);
+            w[i] = Self::block_word(block, i);
             i += 1;
         }
 
@@ -247,9 +382,29 @@ pub trait ShaFamily: 'static {
 //  32-bit family (SHA-224, SHA-256)
 // ============================================================================
 
+/// The 32-bit SHA-2 family (SHA-224, SHA-256).
 #[cfg(any(feature = "sha224", feature = "sha256",))]
 pub struct Sha2_32;
 
+#[cfg(any(feature = "sha224", feature = "sha256",))]
+static SHA2_32_BACKEND: Slot = Slot::new();
+
+#[cfg(any(feature = "sha224", feature = "sha256",))]
+impl Sealed for Sha2_32 {}
+
+/// SHA-224/256 round constants.
+#[cfg(any(feature = "sha224", feature = "sha256",))]
+pub const K32: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
 #[cfg(any(feature = "sha224", feature = "sha256",))]
 impl ShaFamily for Sha2_32 {
     type Word = u32;
@@ -258,18 +413,99 @@ impl ShaFamily for Sha2_32 {
     const LEN_BYTES: usize = 8;
     const STATE_BYTES: usize = 32; // 8 × 4
 
-    const K: &'static [u32] = &[
-        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
-        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
-        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
-        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
-        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
-        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
-        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
-        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
-        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
-        0xc67178f2,
-    ];
+    const K: &'static [u32] = &K32;
+
+    type Block = [u8; 64];
+
+    #[inline(always)]
+    fn as_block<const N: usize>(bytes: &[u8; N]) -> &[u8; 64] {
+        const { assert!(N == 64, "SHA-224/256 blocks are 64 bytes") };
+        bytes.first_chunk().unwrap()
+    }
+
+    #[inline(always)]
+    fn block_word(block: &[u8; 64], i: usize) -> u32 {
+        u32::from_be_bytes(*block[i * 4..].first_chunk().unwrap())
+    }
+
+    fn supports_backend(backend: Backend) -> bool {
+        match backend {
+            Backend::Portable => true,
+            Backend::PortableUnrolled => cfg!(all(
+                feature = "sha2-compress-unrolled",
+                not(feature = "sha2-compress-small")
+            )),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::ShaNi => super::sha256_shani::detect(),
+            Backend::Simd128 => cfg!(all(
+                feature = "simd128",
+                target_arch = "wasm32",
+                target_feature = "simd128"
+            )),
+            _ => false,
+        }
+    }
+
+    #[inline(always)]
+    fn active_backend() -> Backend {
+        SHA2_32_BACKEND.get(|| {
+            select(
+                &[
+                    Backend::ShaNi,
+                    Backend::Simd128,
+                    Backend::PortableUnrolled,
+                    Backend::Portable,
+                ],
+                Self::supports_backend,
+            )
+        })
+    }
+
+    fn force_backend(backend: Option<Backend>) -> Result<(), &'static str> {
+        if backend.is_some_and(|b| !Self::supports_backend(b)) {
+            return Err("backend not supported for SHA-224/256 here");
+        }
+        SHA2_32_BACKEND.set(backend);
+        Ok(())
+    }
+
+    fn hash_batch<const D: usize>(
+        iv: &[u32; 8],
+        inputs: &[&[u8]],
+        outputs: &mut [[u8; D]],
+    ) -> bool {
+        // One SHA-NI stream per message outruns the SIMD lanes.
+        if Self::active_backend() == Backend::ShaNi {
+            return false;
+        }
+        super::multi::hash_many(iv, inputs, outputs);
+        true
+    }
+
+    #[inline(always)]
+    fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+        match Self::active_backend() {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            // SAFETY: `ShaNi` is only selected or forced once
+            // `supports_backend` has detected the instructions.
+            Backend::ShaNi => unsafe { super::sha256_shani::compress(state, block) },
+            #[cfg(all(
+                feature = "simd128",
+                target_arch = "wasm32",
+                target_feature = "simd128"
+            ))]
+            Backend::Simd128 => super::sha256_simd128::compress(state, block),
+            #[cfg(all(
+                feature = "sha2-compress-unrolled",
+                not(feature = "sha2-compress-small")
+            ))]
+            Backend::PortableUnrolled => Self::compress_unrolled(state, block),
+            #[cfg(feature = "sha2-compress-small")]
+            _ => Self::compress_small(state, block),
+            #[cfg(not(feature = "sha2-compress-small"))]
+            _ => Self::compress_rolled(state, block),
+        }
+    }
 
     #[inline(always)]
     fn small_sigma0(x: u32) -> u32 {
@@ -306,6 +542,7 @@ impl ShaFamily for Sha2_32 {
 //  64-bit family (SHA-384, SHA-512, SHA-512/256)
 // ============================================================================
 
+/// The 64-bit SHA-2 family (SHA-384, SHA-512, SHA-512/224, SHA-512/256).
 #[cfg(any(
     feature = "sha384",
     feature = "sha512",
@@ -314,6 +551,112 @@ impl ShaFamily for Sha2_32 {
 ))]
 pub struct Sha2_64;
 
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+static SHA2_64_BACKEND: Slot = Slot::new();
+
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+impl Sealed for Sha2_64 {}
+
+/// SHA-384/512/512-224/512-256 round constants.
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub const K64: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
 #[cfg(any(
     feature = "sha384",
     feature = "sha512",
@@ -327,88 +670,70 @@ impl ShaFamily for Sha2_64 {
     const LEN_BYTES: usize = 16;
     const STATE_BYTES: usize = 64; // 8 × 8
 
-    const K: &'static [u64] = &[
-        0x428a2f98d728ae22,
-        0x7137449123ef65cd,
-        0xb5c0fbcfec4d3b2f,
-        0xe9b5dba58189dbbc,
-        0x3956c25bf348b538,
-        0x59f111f1b605d019,
-        0x923f82a4af194f9b,
-        0xab1c5ed5da6d8118,
-        0xd807aa98a3030242,
-        0x12835b0145706fbe,
-        0x243185be4ee4b28c,
-        0x550c7dc3d5ffb4e2,
-        0x72be5d74f27b896f,
-        0x80deb1fe3b1696b1,
-        0x9bdc06a725c71235,
-        0xc19bf174cf692694,
-        0xe49b69c19ef14ad2,
-        0xefbe4786384f25e3,
-        0x0fc19dc68b8cd5b5,
-        0x240ca1cc77ac9c65,
-        0x2de92c6f592b0275,
-        0x4a7484aa6ea6e483,
-        0x5cb0a9dcbd41fbd4,
-        0x76f988da831153b5,
-        0x983e5152ee66dfab,
-        0xa831c66d2db43210,
-        0xb00327c898fb213f,
-        0xbf597fc7beef0ee4,
-        0xc6e00bf33da88fc2,
-        0xd5a79147930aa725,
-        0x06ca6351e003826f,
-        0x142929670a0e6e70,
-        0x27b70a8546d22ffc,
-        0x2e1b21385c26c926,
-        0x4d2c6dfc5ac42aed,
-        0x53380d139d95b3df,
-        0x650a73548baf63de,
-        0x766a0abb3c77b2a8,
-        0x81c2c92e47edaee6,
-        0x92722c851482353b,
-        0xa2bfe8a14cf10364,
-        0xa81a664bbc423001,
-        0xc24b8b70d0f89791,
-        0xc76c51a30654be30,
-        0xd192e819d6ef5218,
-        0xd69906245565a910,
-        0xf40e35855771202a,
-        0x106aa07032bbd1b8,
-        0x19a4c116b8d2d0c8,
-        0x1e376c085141ab53,
-        0x2748774cdf8eeb99,
-        0x34b0bcb5e19b48a8,
-        0x391c0cb3c5c95a63,
-        0x4ed8aa4ae3418acb,
-        0x5b9cca4f7763e373,
-        0x682e6ff3d6b2b8a3,
-        0x748f82ee5defb2fc,
-        0x78a5636f43172f60,
-        0x84c87814a1f0ab72,
-        0x8cc702081a6439ec,
-        0x90befffa23631e28,
-        0xa4506cebde82bde9,
-        0xbef9a3f7b2c67915,
-        0xc67178f2e372532b,
-        0xca273eceea26619c,
-        0xd186b8c721c0c207,
-        0xeada7dd6cde0eb1e,
-        0xf57d4f7fee6ed178,
-        0x06f067aa72176fba,
-        0x0a637dc5a2c898a6,
-        0x113f9804bef90dae,
-        0x1b710b35131c471b,
-        0x28db77f523047d84,
-        0x32caab7b40c72493,
-        0x3c9ebe0a15c9bebc,
-        0x431d67c49c100d4c,
-        0x4cc5d4becb3e42b6,
-        0x597f299cfc657e2a,
-        0x5fcb6fab3ad6faec,
-        0x6c44198c4a475817,
-    ];
+    const K: &'static [u64] = &K64;
+
+    type Block = [u8; 128];
+
+    #[inline(always)]
+    fn as_block<const N: usize>(bytes: &[u8; N]) -> &[u8; 128] {
+        const { assert!(N == 128, "SHA-384/512 blocks are 128 bytes") };
+        bytes.first_chunk().unwrap()
+    }
+
+    #[inline(always)]
+    fn block_word(block: &[u8; 128], i: usize) -> u64 {
+        u64::from_be_bytes(*block[i * 8..].first_chunk().unwrap())
+    }
+
+    fn supports_backend(backend: Backend) -> bool {
+        match backend {
+            Backend::Portable => true,
+            Backend::PortableUnrolled => cfg!(all(
+                feature = "sha2-compress-unrolled",
+                not(feature = "sha2-compress-small")
+            )),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => super::sha512_avx2::detect(),
+            _ => false,
+        }
+    }
+
+    #[inline(always)]
+    fn active_backend() -> Backend {
+        SHA2_64_BACKEND.get(|| {
+            select(
+                &[Backend::Avx2, Backend::PortableUnrolled, Backend::Portable],
+                Self::supports_backend,
+            )
+        })
+    }
+
+    fn force_backend(backend: Option<Backend>) -> Result<(), &'static str> {
+        if backend.is_some_and(|b| !Self::supports_backend(b)) {
+            return Err("backend not supported for SHA-384/512 here");
+        }
+        SHA2_64_BACKEND.set(backend);
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn compress(state: &mut [u64; 8], block: &[u8; 128]) {
+        match Self::active_backend() {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            // SAFETY: `Avx2` is only selected or forced once
+            // `supports_backend` has detected AVX2 and BMI2.
+            Backend::Avx2 => unsafe { super::sha512_avx2::compress(state, block) },
+            #[cfg(all(
+                feature = "sha2-compress-unrolled",
+                not(feature = "sha2-compress-small")
+            ))]
+            Backend::PortableUnrolled => Self::compress_unrolled(state, block),
+            #[cfg(feature = "sha2-compress-small")]
+            _ => Self::compress_small(state, block),
+            #[cfg(not(feature = "sha2-compress-small"))]
+            _ => Self::compress_rolled(state, block),
+        }
+    }
 
     #[inline(always)]
     fn small_sigma0(x: u64) -> u64 {
@@ -0,0 +1,136 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! SHA-256 of 32- and 64-byte messages, the inputs Merkle trees and hash
+//! chains hash over and over.
+//!
+//! Both lengths have padding known in advance, so neither goes through the
+//! hasher's buffer or length accounting. A 32-byte message shares its only
+//! block with a constant padding tail. A 64-byte message is followed by a
+//! constant padding block, whose message schedule (plus round constants) is
+//! computed at compile time for the portable backends.
+
+use super::backend::Backend;
+use super::family::{K32, Sha2_32, ShaFamily};
+use super::variant::{Sha256Cfg, ShaVariant};
+
+/// The block following a 64-byte message: `0x80`, zeros, bit length 512.
+pub(super) const PAD64_BLOCK: [u8; 64] = {
+    let mut block = [0u8; 64];
+    block[0] = 0x80;
+    block[62] = 0x02;
+    block
+};
+
+/// `W[t] + K[t]` for every round of [`PAD64_BLOCK`].
+pub(super) const PAD64_WK: [u32; 64] = {
+    const fn sigma0(x: u32) -> u32 {
+        x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+    }
+    const fn sigma1(x: u32) -> u32 {
+        x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+    }
+
+    let mut w = [0u32; 64];
+    let mut t = 0;
+    while t < 16 {
+        w[t] = u32::from_be_bytes([
+            PAD64_BLOCK[t * 4],
+            PAD64_BLOCK[t * 4 + 1],
+            PAD64_BLOCK[t * 4 + 2],
+            PAD64_BLOCK[t * 4 + 3],
+        ]);
+        t += 1;
+    }
+    while t < 64 {
+        w[t] = sigma1(w[t - 2])
+            .wrapping_add(w[t - 7])
+            .wrapping_add(sigma0(w[t - 15]))
+            .wrapping_add(w[t - 16]);
+        t += 1;
+    }
+
+    t = 0;
+    while t < 64 {
+        w[t] = w[t].wrapping_add(K32[t]);
+        t += 1;
+    }
+    w
+};
+
+/// SHA-256 of a 32-byte message, e.g. one step of a hash chain.
+#[inline]
+pub fn sha256_32bytes(data: &[u8; 32]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    block[..32].copy_from_slice(data);
+    block[32] = 0x80;
+    block[62] = 0x01; // 256 bits
+
+    let mut state = Sha256Cfg::IV;
+    Sha2_32::compress(&mut state, &block);
+    output(&state)
+}
+
+/// SHA-256 of a 64-byte message, e.g. two 32-byte Merkle tree children.
+#[inline]
+pub fn sha256_64bytes(data: &[u8; 64]) -> [u8; 32] {
+    let mut state = Sha256Cfg::IV;
+    Sha2_32::compress(&mut state, data);
+    match Sha2_32::active_backend() {
+        Backend::Portable | Backend::PortableUnrolled => rounds(&mut state, &PAD64_WK),
+        // Hardware backends expand the schedule faster than it loads.
+        _ => Sha2_32::compress(&mut state, &PAD64_BLOCK),
+    }
+    output(&state)
+}
+
+/// The 64 rounds and feed-forward of the compression function over a
+/// precomputed `W[t] + K[t]` schedule.
+#[inline(always)]
+pub(super) fn rounds(state: &mut [u32; 8], wk: &[u32; 64]) {
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for &wk in wk {
+        let ch = g ^ (e & (f ^ g));
+        let maj = (a & b) ^ (c & (a ^ b));
+        let t1 = h
+            .wrapping_add(Sha2_32::big_sigma1(e))
+            .wrapping_add(ch)
+            .wrapping_add(wk);
+        let t2 = Sha2_32::big_sigma0(a).wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[inline(always)]
+fn output(state: &[u32; 8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    for (dst, w) in out.chunks_exact_mut(4).zip(state) {
+        dst.copy_from_slice(&w.to_be_bytes());
+    }
+    out
+}
@@ -0,0 +1,119 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! `expand_message_xmd` (RFC 9380, section 5.3.1), the expander behind
+//! hash-to-curve suites such as `BLS12381G2_XMD:SHA-256_SSWU_RO_` and the
+//! OPRF suites of RFC 9497.
+//!
+//! Every message starts with one block of zeros, so [`ExpandMsgXmd`]
+//! compresses it once per domain separation tag and clones the midstate
+//! for each message.
+
+use super::{hasher::ShaHasher, variant::ShaVariant};
+
+/// Longest output in bytes; also at most 255 hash lengths.
+pub const MAX_OUTPUT_BYTES: usize = u16::MAX as usize;
+
+/// `expand_message_xmd` bound to one domain separation tag.
+pub struct ExpandMsgXmd<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    /// State after the all-zero block `Z_pad`.
+    z_pad: ShaHasher<V, BLOCK, DIGEST>,
+    dst: [u8; 255],
+    dst_len: u8,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for ExpandMsgXmd<V, B, D> {
+    fn clone(&self) -> Self {
+        Self {
+            z_pad: self.z_pad.clone(),
+            dst: self.dst,
+            dst_len: self.dst_len,
+        }
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> ExpandMsgXmd<V, B, D> {
+    /// Bind the expander to `dst`. Tags longer than 255 bytes are replaced
+    /// by `H("H2C-OVERSIZE-DST-" || dst)` (RFC 9380, section 5.3.3).
+    pub fn new(dst: &[u8]) -> Self {
+        let mut z_pad = ShaHasher::new();
+        z_pad.update_blocks(&[[0u8; B]]);
+
+        let mut expander = Self {
+            z_pad,
+            dst: [0; 255],
+            dst_len: 0,
+        };
+        if dst.len() > 255 {
+            let mut h = ShaHasher::<V, B, D>::new();
+            h.update(b"H2C-OVERSIZE-DST-");
+            expander.set_dst(&h.finalize_with(dst));
+        } else {
+            expander.set_dst(dst);
+        }
+        expander
+    }
+
+    /// The tag in use, after any oversize reduction.
+    pub fn dst(&self) -> &[u8] {
+        &self.dst[..self.dst_len as usize]
+    }
+
+    /// Fill `out` with `expand_message_xmd(msg, DST, out.len())`.
+    ///
+    /// Fails if `out` is longer than 255 hash lengths or
+    /// [`MAX_OUTPUT_BYTES`].
+    pub fn expand(&self, msg: &[u8], out: &mut [u8]) -> Result<(), &'static str> {
+        if out.len().div_ceil(D) > 255 || out.len() > MAX_OUTPUT_BYTES {
+            return Err("output too long");
+        }
+        let dst = self.dst();
+        let dst_len = [self.dst_len];
+
+        let mut h = self.z_pad.clone();
+        h.update(msg);
+        h.update(&(out.len() as u16).to_be_bytes());
+        h.update(&[0x00]);
+        h.update(dst);
+        let b_0 = h.finalize_with(&dst_len);
+
+        // b_1 = H(b_0 || 1 || DST'), which is b_i with b_0 = b_0 ^ 0.
+        let mut b_i = [0u8; D];
+        for (i, chunk) in (1..=255u8).zip(out.chunks_mut(D)) {
+            let chained: [u8; D] = ::core::array::from_fn(|j| b_0[j] ^ b_i[j]);
+            let mut h = ShaHasher::<V, B, D>::new();
+            h.update(&chained);
+            h.update(&[i]);
+            h.update(dst);
+            b_i = h.finalize_with(&dst_len);
+            chunk.copy_from_slice(&b_i[..chunk.len()]);
+        }
+        Ok(())
+    }
+
+    fn set_dst(&mut self, dst: &[u8]) {
+        self.dst[..dst.len()].copy_from_slice(dst);
+        self.dst_len = dst.len() as u8;
+    }
+}
+
+/// One-shot `expand_message_xmd(msg, dst, out.len())`.
+pub fn expand_message_xmd<V: ShaVariant, const B: usize, const D: usize>(
+    msg: &[u8],
+    dst: &[u8],
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    ExpandMsgXmd::<V, B, D>::new(dst).expand(msg, out)
+}
@@ -30,6 +30,33 @@ use {
 ///
 /// `BLOCK` = block size in bytes, `DIGEST` = output length in bytes.
 /// Use the type aliases in `lib.rs` instead of spelling these out.
+///
+/// Both parameters must agree with the variant; mismatched instantiations
+/// fail to compile:
+///
+/// ```compile_fail
+/// # #[cfg(feature = "sha256")] {
+/// let _ = sha2::ShaHasher::<sha2::Sha256Cfg, 128, 28>::new();
+/// # }
+/// ```
+///
+/// This includes variants that override `BLOCK_BYTES`:
+///
+/// ```compile_fail
+/// # #[cfg(feature = "sha256")] {
+/// use sha2::{Sha256Cfg, Sha2_32, ShaHasher, ShaVariant};
+///
+/// struct WideCfg;
+/// impl ShaVariant for WideCfg {
+///     type Family = Sha2_32;
+///     const IV: [u32; 8] = Sha256Cfg::IV;
+///     const DIGEST_BYTES: usize = 32;
+///     const BLOCK_BYTES: usize = 128;
+/// }
+///
+/// let _ = ShaHasher::<WideCfg, 128, 32>::new();
+/// # }
+/// ```
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -46,6 +73,27 @@ pub struct ShaHasher<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
     pub(crate) _variant: PhantomData<V>,
 }
 
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for ShaHasher<V, B, D> {
+    /// Fork the hasher, e.g. to take a digest of a prefix and keep going.
+    fn clone(&self) -> Self {
+        Self {
+            engine: self.engine.clone(),
+            buffer: self.buffer,
+            buffer_len: self.buffer_len,
+            total_len: self.total_len,
+            _variant: PhantomData,
+        }
+    }
+}
+
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -69,8 +117,35 @@ impl<V: ShaVariant, const B: usize, const D: usize> Default for ShaHasher<V, B,
     feature = "sha512_256"
 ))]
 impl<V: ShaVariant, const B: usize, const D: usize> ShaHasher<V, B, D> {
+    /// Digest length in bytes.
+    pub const OUTPUT_SIZE: usize = D;
+    /// Block size in bytes.
+    pub const BLOCK_SIZE: usize = B;
+    /// Serialised checkpoint size in bytes.
+    pub const STATE_SIZE: usize = V::SERIAL_BYTES;
+
+    /// Rejects `BLOCK`/`DIGEST` parameters that disagree with `V`. The
+    /// block size is checked against the sealed family, so a variant
+    /// cannot override `BLOCK_BYTES` to get past it.
+    pub(super) const PARAMS_OK: () = {
+        assert!(
+            B == <V::Family as ShaFamily>::BLOCK_BYTES,
+            "BLOCK does not match the variant's family"
+        );
+        assert!(
+            V::BLOCK_BYTES == <V::Family as ShaFamily>::BLOCK_BYTES,
+            "BLOCK_BYTES must not be overridden"
+        );
+        assert!(D == V::DIGEST_BYTES, "DIGEST does not match the variant");
+        assert!(
+            D > 0 && D <= <V::Family as ShaFamily>::STATE_BYTES,
+            "DIGEST_BYTES must be between 1 and the family state size"
+        );
+    };
+
     /// Create a new hasher initialised with the variant's IV.
     pub fn new() -> Self {
+        let () = Self::PARAMS_OK;
         Self {
             engine: ShaEngine::new(V::IV),
             buffer: [0u8; B],
@@ -90,6 +165,8 @@ impl<V: ShaVariant, const B: usize, const D: usize> ShaHasher<V, B, D> {
     /// Feed data into the hasher.  May be called repeatedly.
     #[inline(always)]
     pub fn update(&mut self, data: &[u8]) {
+        type F<V> = <V as ShaVariant>::Family;
+
         self.total_len += data.len() as u128;
         let mut offset = 0;
 
@@ -97,7 +174,7 @@ impl<V: ShaVariant, const B: usize, const D: usize> ShaHasher<V, B, D> {
             let needed = B - self.buffer_len;
             if data.len() >= needed {
                 self.buffer[self.buffer_len..B].copy_from_slice(&data[..needed]);
-                self.engine.compress(&self.buffer);
+                self.engine.compress(F::<V>::as_block(&self.buffer));
                 self.buffer_len = 0;
                 offset = needed;
             } else {
@@ -107,59 +184,182 @@ impl<V: ShaVariant, const B: usize, const D: usize> ShaHasher<V, B, D> {
             }
         }
 
-        while offset + B <= data.len() {
-            self.engine.compress(&data[offset..offset + B]);
-            offset += B;
+        let blocks = data[offset..].chunks_exact(B);
+        let remainder = blocks.remainder();
+        for block in blocks {
+            let block: &[u8; B] = block.try_into().unwrap();
+            self.engine.compress(F::<V>::as_block(block));
         }
 
-        let remainder = &data[offset..];
         if !remainder.is_empty() {
             self.buffer[..remainder.len()].copy_from_slice(remainder);
             self.buffer_len = remainder.len();
         }
     }
 
+    /// Feed whole blocks, e.g. from block-aligned storage.
+    ///
+    /// With no partial block pending, the blocks are compressed in place
+    /// without touching the internal buffer; otherwise this is the same as
+    /// [`update`](Self::update) on the flattened bytes.
+    #[inline]
+    pub fn update_blocks(&mut self, blocks: &[[u8; B]]) {
+        type F<V> = <V as ShaVariant>::Family;
+
+        if self.buffer_len != 0 {
+            return self.update(blocks.as_flattened());
+        }
+
+        self.total_len += (blocks.len() * B) as u128;
+        for block in blocks {
+            self.engine.compress(F::<V>::as_block(block));
+        }
+    }
+
     /// Finalize and return the digest.
     #[inline(always)]
     pub fn finalize(&mut self) -> [u8; D] {
+        Self::pad(
+            &mut self.engine,
+            self.total_len,
+            &mut self.buffer,
+            self.buffer_len,
+        );
+        self.output()
+    }
+
+    /// Feed `data` and finalize, as `update(data)` then `finalize()`.
+    ///
+    /// When no partial block is pending, whole blocks of `data` are
+    /// compressed in place and only its final partial block is copied, once,
+    /// into the padding block; the internal buffer is not used.
+    #[inline(always)]
+    pub fn finalize_with(&mut self, data: &[u8]) -> [u8; D] {
+        type F<V> = <V as ShaVariant>::Family;
+
+        if self.buffer_len != 0 {
+            self.update(data);
+            return self.finalize();
+        }
+
+        self.total_len += data.len() as u128;
+        let blocks = data.chunks_exact(B);
+        let tail = blocks.remainder();
+        for block in blocks {
+            let block: &[u8; B] = block.try_into().unwrap();
+            self.engine.compress(F::<V>::as_block(block));
+        }
+
+        let mut last = [0u8; B];
+        last[..tail.len()].copy_from_slice(tail);
+        Self::pad(&mut self.engine, self.total_len, &mut last, tail.len());
+        self.output()
+    }
+
+    /// Pad the message whose final `len` bytes open `block` and compress
+    /// the last block or two.
+    #[inline(always)]
+    fn pad(engine: &mut ShaEngine<V::Family>, total_len: u128, block: &mut [u8; B], len: usize) {
         type F<V> = <V as ShaVariant>::Family;
         let pad_threshold = B - <F<V> as ShaFamily>::LEN_BYTES;
 
         // 0x80 sentinel
-        self.buffer[self.buffer_len] = 0x80;
-        self.buffer_len += 1;
+        block[len] = 0x80;
+        let mut len = len + 1;
 
         // If no room for the length field, flush an extra block.
-        if self.buffer_len > pad_threshold {
-            self.buffer[self.buffer_len..B].fill(0);
-            self.engine.compress(&self.buffer);
-            self.buffer_len = 0;
+        if len > pad_threshold {
+            block[len..B].fill(0);
+            engine.compress(F::<V>::as_block(block));
+            len = 0;
         }
 
         // Zero-fill up to the length field.
-        self.buffer[self.buffer_len..pad_threshold].fill(0);
+        block[len..pad_threshold].fill(0);
 
         // Append bit-length in big-endian.
-        <F<V>>::encode_bit_len(self.total_len, &mut self.buffer[pad_threshold..]);
-        self.engine.compress(&self.buffer);
+        <F<V>>::encode_bit_len(total_len, &mut block[pad_threshold..]);
+        engine.compress(F::<V>::as_block(block));
+    }
 
-        // Export state and truncate to DIGEST bytes.
+    /// Export the state, truncated to `D` bytes.
+    #[inline(always)]
+    fn output(&self) -> [u8; D] {
         // 64 bytes is the largest possible state (8 × u64).
         let mut full = [0u8; 64];
         self.engine
-            .export(&mut full[..<F<V> as ShaFamily>::STATE_BYTES]);
+            .export(&mut full[..<V::Family as ShaFamily>::STATE_BYTES]);
 
         let mut out = [0u8; D];
         out.copy_from_slice(&full[..D]);
         out
     }
 
+    /// Resume hashing from a published digest, as if `len` bytes of message
+    /// (plus their padding) had already been processed.
+    ///
+    /// Only valid for variants whose digest is the full chaining state
+    /// (SHA-256, SHA-512); truncated variants are rejected at compile time.
+    /// Data fed afterwards hashes as `message || glue_padding(len) || data`.
+    pub fn from_digest_and_len(digest: &[u8; D], len: u128) -> Self {
+        const {
+            assert!(
+                D == <V::Family as ShaFamily>::STATE_BYTES,
+                "from_digest_and_len requires a non-truncated variant"
+            )
+        };
+        let () = Self::PARAMS_OK;
+
+        Self {
+            engine: ShaEngine::new(ShaEngine::<V::Family>::import(digest)),
+            buffer: [0u8; B],
+            buffer_len: 0,
+            total_len: len + <V::Family>::padding_len(len) as u128,
+            _variant: PhantomData,
+        }
+    }
+
+    /// Write the padding [`finalize`](Self::finalize) appends to a
+    /// `len`-byte message into `out`, returning its length.
+    ///
+    /// The result is between `LEN_BYTES + 1` and `BLOCK + LEN_BYTES` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than the padding.
+    pub fn glue_padding(len: u128, out: &mut [u8]) -> usize {
+        <V::Family>::write_padding(len, out)
+    }
+
     /// One-shot convenience.
     #[cfg(feature = "sync")]
     #[inline]
     pub fn digest(data: &[u8]) -> [u8; D] {
-        let mut h = Self::new();
-        h.update(data);
-        h.finalize()
+        Self::new().finalize_with(data)
+    }
+
+    /// Hash every message in `inputs` into the matching slot of `outputs`,
+    /// as [`digest`](Self::digest) on each.
+    ///
+    /// SHA-224/256 variants hash several messages at once on SIMD lanes
+    /// unless SHA-NI is in use, which is faster one message at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `outputs` differ in length.
+    #[cfg(feature = "sync")]
+    pub fn hash_batch(inputs: &[&[u8]], outputs: &mut [[u8; D]]) {
+        assert_eq!(
+            inputs.len(),
+            outputs.len(),
+            "inputs and outputs must have the same length"
+        );
+
+        if <V::Family>::hash_batch(&V::IV, inputs, outputs) {
+            return;
+        }
+        for (input, output) in inputs.iter().zip(outputs) {
+            *output = Self::digest(input);
+        }
     }
 }
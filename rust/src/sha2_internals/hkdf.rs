@@ -0,0 +1,94 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! HKDF (RFC 5869) over any SHA-2 variant.
+
+use super::{hmac::Hmac, variant::ShaVariant};
+
+/// A pseudorandom key from HKDF-Extract, ready for any number of
+/// HKDF-Expand calls.
+pub struct Hkdf<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    prk: [u8; DIGEST],
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for Hkdf<V, B, D> {
+    fn clone(&self) -> Self {
+        Self::from_prk(&self.prk)
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Hkdf<V, B, D> {
+    /// Longest output of a single expansion: 255 hash lengths.
+    pub const MAX_OUTPUT: usize = 255 * D;
+
+    /// HKDF-Extract. An empty `salt` stands for `DIGEST` zero bytes.
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        Self::from_prk(&Hmac::<V, B, D>::mac(salt, ikm))
+    }
+
+    /// Skip extraction and expand an existing pseudorandom key.
+    pub fn from_prk(prk: &[u8; D]) -> Self {
+        Self {
+            prk: *prk,
+            _variant: ::core::marker::PhantomData,
+        }
+    }
+
+    /// The pseudorandom key.
+    pub fn prk(&self) -> &[u8; D] {
+        &self.prk
+    }
+
+    /// HKDF-Expand: fill `okm` from the key and `info`.
+    ///
+    /// Fails if `okm` is longer than [`MAX_OUTPUT`](Self::MAX_OUTPUT).
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) -> Result<(), &'static str> {
+        self.expand_parts(&[info], okm)
+    }
+
+    /// HKDF-Expand with `info` given as the concatenation of `parts`.
+    pub(super) fn expand_parts(&self, info: &[&[u8]], okm: &mut [u8]) -> Result<(), &'static str> {
+        if okm.len() > Self::MAX_OUTPUT {
+            return Err("output longer than 255 hash lengths");
+        }
+
+        let keyed = Hmac::<V, B, D>::new(&self.prk);
+        let mut t = [0u8; D];
+        for (i, chunk) in (1..=255u8).zip(okm.chunks_mut(D)) {
+            let mut mac = keyed.clone();
+            if i > 1 {
+                mac.update(&t);
+            }
+            for part in info {
+                mac.update(part);
+            }
+            mac.update(&[i]);
+            t = mac.finalize();
+            chunk.copy_from_slice(&t[..chunk.len()]);
+        }
+        Ok(())
+    }
+
+    /// One-shot extract-then-expand.
+    pub fn derive(
+        salt: &[u8],
+        ikm: &[u8],
+        info: &[u8],
+        okm: &mut [u8],
+    ) -> Result<(), &'static str> {
+        Self::extract(salt, ikm).expand(info, okm)
+    }
+}
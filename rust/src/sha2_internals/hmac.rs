@@ -0,0 +1,105 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! HMAC (RFC 2104, FIPS 198-1) over any SHA-2 variant.
+
+use super::{hasher::ShaHasher, variant::ShaVariant};
+
+/// Incremental HMAC keyed once, generic over any [`ShaVariant`].
+///
+/// The key is absorbed into inner and outer midstates up front, so each
+/// tag costs two compressions more than the message itself.
+pub struct Hmac<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    inner: ShaHasher<V, BLOCK, DIGEST>,
+    /// State after `K ^ ipad`, restored on reset.
+    ikey: ShaHasher<V, BLOCK, DIGEST>,
+    /// State after `K ^ opad`.
+    okey: ShaHasher<V, BLOCK, DIGEST>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for Hmac<V, B, D> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            ikey: self.ikey.clone(),
+            okey: self.okey.clone(),
+        }
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Hmac<V, B, D> {
+    /// Tag length in bytes.
+    pub const OUTPUT_SIZE: usize = D;
+
+    /// Key a new MAC. Keys longer than a block are hashed first.
+    pub fn new(key: &[u8]) -> Self {
+        let mut k = [0u8; B];
+        if key.len() > B {
+            k[..D].copy_from_slice(&ShaHasher::<V, B, D>::new().finalize_with(key));
+        } else {
+            k[..key.len()].copy_from_slice(key);
+        }
+
+        let mut ikey = ShaHasher::new();
+        ikey.update_blocks(&[k.map(|b| b ^ 0x36)]);
+        let mut okey = ShaHasher::new();
+        okey.update_blocks(&[k.map(|b| b ^ 0x5c)]);
+
+        Self {
+            inner: ikey.clone(),
+            ikey,
+            okey,
+        }
+    }
+
+    /// Return to the freshly keyed state.
+    pub fn reset(&mut self) {
+        self.inner = self.ikey.clone();
+    }
+
+    /// Feed message data.  May be called repeatedly.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Return the tag and reset for the next message under the same key.
+    pub fn finalize(&mut self) -> [u8; D] {
+        let inner = self.inner.finalize();
+        self.reset();
+        self.okey.clone().finalize_with(&inner)
+    }
+
+    /// Compare the tag against `tag` in constant time and reset.
+    pub fn verify(&mut self, tag: &[u8; D]) -> bool {
+        ct_eq(&self.finalize(), tag)
+    }
+
+    /// One-shot convenience.
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; D] {
+        let mut h = Self::new(key);
+        h.update(data);
+        h.finalize()
+    }
+}
+
+/// Whether `a == b`, in time that depends only on the lengths.
+pub(super) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    ::core::hint::black_box(diff) == 0
+}
@@ -0,0 +1,316 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Key derivation functions from NIST SP 800-108 Rev. 1 (KBKDF in
+//! counter, feedback and double-pipeline mode, with HMAC as the PRF) and
+//! SP 800-56C Rev. 2 (the one-step KDF with a hash or HMAC, and the ANSI
+//! X9.63 variant).
+//!
+//! Every function fills its whole output slice, whose length is `L`.
+
+use super::{hasher::ShaHasher, hmac::Hmac, variant::ShaVariant};
+
+// ---- SP 800-108 ------------------------------------------------------------
+
+/// Where the counter `[i]_r` goes in each PRF input. The chaining value
+/// is `K(i-1)` (or the IV) in feedback mode, `A(i)` in double-pipeline
+/// mode and empty in counter mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterPosition {
+    /// `[i] || chain || fixed`.
+    BeforeChain,
+    /// `chain || [i] || fixed`; in counter mode the same as
+    /// [`BeforeChain`](Self::BeforeChain).
+    AfterChain,
+    /// `chain || fixed[..n] || [i] || fixed[n..]`.
+    Middle(usize),
+    /// `chain || fixed || [i]`.
+    AfterFixed,
+}
+
+/// Counter layout of a KBKDF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KbkdfParams {
+    /// Counter width `r` in bits: 8, 16, 24 or 32.
+    pub counter_bits: u32,
+    /// Counter position, or `None` for no counter. Counter mode requires
+    /// one.
+    pub counter: Option<CounterPosition>,
+}
+
+impl Default for KbkdfParams {
+    /// A 32-bit counter ahead of everything else.
+    fn default() -> Self {
+        Self {
+            counter_bits: 32,
+            counter: Some(CounterPosition::BeforeChain),
+        }
+    }
+}
+
+/// The fixed input data of a KBKDF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixedInput<'a> {
+    /// Used verbatim, as in the CAVP vectors.
+    Raw(&'a [u8]),
+    /// `Label || 0x00 || Context || [L]`, the encoding of SP 800-108
+    /// section 4, with `L` (the output length in bits) written big-endian
+    /// in `l_bits` bits: 8, 16, 24 or 32.
+    LabelContext {
+        /// Purpose of the derived keying material.
+        label: &'a [u8],
+        /// Information about the parties deriving it.
+        context: &'a [u8],
+        /// Width of the encoded `L`.
+        l_bits: u32,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Counter,
+    Feedback,
+    DoublePipeline,
+}
+
+/// KBKDF in counter mode (SP 800-108 section 4.1).
+pub fn counter<V: ShaVariant, const B: usize, const D: usize>(
+    params: KbkdfParams,
+    key: &[u8],
+    fixed: FixedInput<'_>,
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    kbkdf::<V, B, D>(Mode::Counter, params, key, &[], fixed, out)
+}
+
+/// KBKDF in feedback mode (SP 800-108 section 4.2), chaining from `iv`,
+/// which may be empty.
+pub fn feedback<V: ShaVariant, const B: usize, const D: usize>(
+    params: KbkdfParams,
+    key: &[u8],
+    iv: &[u8],
+    fixed: FixedInput<'_>,
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    kbkdf::<V, B, D>(Mode::Feedback, params, key, iv, fixed, out)
+}
+
+/// KBKDF in double-pipeline mode (SP 800-108 section 4.3).
+pub fn double_pipeline<V: ShaVariant, const B: usize, const D: usize>(
+    params: KbkdfParams,
+    key: &[u8],
+    fixed: FixedInput<'_>,
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    kbkdf::<V, B, D>(Mode::DoublePipeline, params, key, &[], fixed, out)
+}
+
+fn kbkdf<V: ShaVariant, const B: usize, const D: usize>(
+    mode: Mode,
+    params: KbkdfParams,
+    key: &[u8],
+    iv: &[u8],
+    fixed: FixedInput<'_>,
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    let counter_bytes =
+        width_bytes(params.counter_bits).ok_or("counter width must be 8, 16, 24 or 32 bits")?;
+    let blocks = out.len().div_ceil(D) as u64;
+    let max_blocks = match params.counter {
+        Some(_) => (1u64 << params.counter_bits) - 1,
+        None if mode == Mode::Counter => return Err("counter mode requires a counter"),
+        None => u32::MAX as u64,
+    };
+    if blocks > max_blocks {
+        return Err("output too long");
+    }
+
+    let l_bytes;
+    let parts: [&[u8]; 4] = match fixed {
+        FixedInput::Raw(raw) => [raw, &[], &[], &[]],
+        FixedInput::LabelContext {
+            label,
+            context,
+            l_bits,
+        } => {
+            let width = width_bytes(l_bits).ok_or("L width must be 8, 16, 24 or 32 bits")?;
+            let l = out.len() as u64 * 8;
+            if l >> l_bits != 0 {
+                return Err("output length does not fit in L");
+            }
+            l_bytes = (l as u32).to_be_bytes();
+            [label, &[0x00], context, &l_bytes[4 - width..]]
+        }
+    };
+    if let Some(CounterPosition::Middle(n)) = params.counter
+        && n > parts.iter().map(|p| p.len()).sum()
+    {
+        return Err("counter position past the fixed input");
+    }
+
+    let mut prf = Hmac::<V, B, D>::new(key);
+    let mut a = [0u8; D];
+    if mode == Mode::DoublePipeline {
+        // A(0) is the fixed input.
+        feed(&mut prf, &parts, None);
+        a = prf.finalize();
+    }
+
+    let mut k = [0u8; D];
+    for (i, chunk) in (1..=u32::MAX).zip(out.chunks_mut(D)) {
+        let ctr = i.to_be_bytes();
+        let ctr = &ctr[4 - counter_bytes..];
+        let chain: &[u8] = match mode {
+            Mode::Counter => &[],
+            Mode::Feedback if i == 1 => iv,
+            Mode::Feedback => &k,
+            Mode::DoublePipeline => {
+                if i > 1 {
+                    prf.update(&a);
+                    a = prf.finalize();
+                }
+                &a
+            }
+        };
+
+        match params.counter {
+            Some(CounterPosition::BeforeChain) => {
+                prf.update(ctr);
+                prf.update(chain);
+                feed(&mut prf, &parts, None);
+            }
+            Some(CounterPosition::AfterChain) => {
+                prf.update(chain);
+                prf.update(ctr);
+                feed(&mut prf, &parts, None);
+            }
+            Some(CounterPosition::Middle(n)) => {
+                prf.update(chain);
+                feed(&mut prf, &parts, Some((n, ctr)));
+            }
+            Some(CounterPosition::AfterFixed) => {
+                prf.update(chain);
+                feed(&mut prf, &parts, None);
+                prf.update(ctr);
+            }
+            None => {
+                prf.update(chain);
+                feed(&mut prf, &parts, None);
+            }
+        }
+        k = prf.finalize();
+        chunk.copy_from_slice(&k[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// Feed the concatenation of `parts`, inserting `insert.1` after the
+/// first `insert.0` bytes.
+fn feed<V: ShaVariant, const B: usize, const D: usize>(
+    prf: &mut Hmac<V, B, D>,
+    parts: &[&[u8]],
+    insert: Option<(usize, &[u8])>,
+) {
+    let Some((mut at, extra)) = insert else {
+        for part in parts {
+            prf.update(part);
+        }
+        return;
+    };
+    let mut inserted = false;
+    for part in parts {
+        if !inserted && at <= part.len() {
+            prf.update(&part[..at]);
+            prf.update(extra);
+            prf.update(&part[at..]);
+            inserted = true;
+        } else {
+            prf.update(part);
+            at = at.saturating_sub(part.len());
+        }
+    }
+}
+
+fn width_bytes(bits: u32) -> Option<usize> {
+    match bits {
+        8 | 16 | 24 | 32 => Some(bits as usize / 8),
+        _ => None,
+    }
+}
+
+// ---- SP 800-56C ------------------------------------------------------------
+
+/// Check that `out` needs at most `2^32 - 1` hash outputs.
+fn check_reps(len: usize, digest_bytes: usize) -> Result<(), &'static str> {
+    if len.div_ceil(digest_bytes) as u64 > u32::MAX as u64 {
+        return Err("output too long");
+    }
+    Ok(())
+}
+
+/// The one-step KDF with a hash (SP 800-56C section 4.1, option 1):
+/// `H(counter || Z || FixedInfo)`, also known as the Concat KDF.
+pub fn one_step_hash<V: ShaVariant, const B: usize, const D: usize>(
+    z: &[u8],
+    fixed_info: &[u8],
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    check_reps(out.len(), D)?;
+    for (i, chunk) in (1..=u32::MAX).zip(out.chunks_mut(D)) {
+        let mut h = ShaHasher::<V, B, D>::new();
+        h.update(&i.to_be_bytes());
+        h.update(z);
+        h.update(fixed_info);
+        chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// The one-step KDF with HMAC (SP 800-56C section 4.1, option 2):
+/// `HMAC(salt, counter || Z || FixedInfo)`. An empty `salt` is the
+/// default all-zero salt.
+pub fn one_step_hmac<V: ShaVariant, const B: usize, const D: usize>(
+    salt: &[u8],
+    z: &[u8],
+    fixed_info: &[u8],
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    check_reps(out.len(), D)?;
+    let mut mac = Hmac::<V, B, D>::new(salt);
+    for (i, chunk) in (1..=u32::MAX).zip(out.chunks_mut(D)) {
+        mac.update(&i.to_be_bytes());
+        mac.update(z);
+        mac.update(fixed_info);
+        chunk.copy_from_slice(&mac.finalize()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// The ANSI X9.63 KDF: `H(Z || counter || SharedInfo)`.
+pub fn x963<V: ShaVariant, const B: usize, const D: usize>(
+    z: &[u8],
+    shared_info: &[u8],
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    check_reps(out.len(), D)?;
+    for (i, chunk) in (1..=u32::MAX).zip(out.chunks_mut(D)) {
+        let mut h = ShaHasher::<V, B, D>::new();
+        h.update(z);
+        h.update(&i.to_be_bytes());
+        h.update(shared_info);
+        chunk.copy_from_slice(&h.finalize()[..chunk.len()]);
+    }
+    Ok(())
+}
@@ -13,10 +13,146 @@
  * PERFORMANCE OF THIS SOFTWARE.
  */
 
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+mod backend;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod drbg;
 mod engine;
 mod family;
+#[cfg(feature = "sha256")]
+mod fixed;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod hash_to_curve;
 mod hasher;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+mod hkdf;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+mod hmac;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod kdf;
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+mod multi;
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+pub mod noise;
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+pub mod otp;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod raw;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod rfc6979;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod rsa_encoding;
+#[cfg(all(feature = "std", any(feature = "sha256", feature = "sha512")))]
+pub mod scram;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "sha224", feature = "sha256")
+))]
+mod sha256_shani;
+#[cfg(all(
+    feature = "simd128",
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    any(feature = "sha224", feature = "sha256")
+))]
+mod sha256_simd128;
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+mod sha512_avx2;
+#[cfg(any(feature = "sha256", feature = "sha512"))]
+pub mod sha_crypt;
 mod state;
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub mod tls;
+#[cfg(all(
+    feature = "std",
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+mod tree;
 mod variant;
 mod word;
 
@@ -32,6 +168,18 @@ mod word;
     )
 ))]
 pub use self::state::ShaState;
+#[cfg(all(
+    feature = "std",
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+pub use self::tree::{TreeHasher, TreeParams};
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -40,12 +188,29 @@ pub use self::state::ShaState;
     feature = "sha512_224",
     feature = "sha512_256"
 ))]
-pub use self::{hasher::ShaHasher, variant::ShaVariant};
+pub use self::{
+    backend::Backend,
+    drbg::{HashDrbg, HmacDrbg},
+    family::ShaFamily,
+    hasher::ShaHasher,
+    hkdf::Hkdf,
+    hmac::Hmac,
+    variant::ShaVariant,
+    word::ShaWord,
+};
+
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+pub use self::family::Sha2_32;
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub use self::family::Sha2_64;
 
 #[cfg(feature = "sha224")]
 pub use self::variant::Sha224Cfg;
-#[cfg(feature = "sha256")]
-pub use self::variant::Sha256Cfg;
 #[cfg(feature = "sha384")]
 pub use self::variant::Sha384Cfg;
 #[cfg(feature = "sha512_224")]
@@ -54,56 +219,170 @@ pub use self::variant::Sha512_224Cfg;
 pub use self::variant::Sha512_256Cfg;
 #[cfg(feature = "sha512")]
 pub use self::variant::Sha512Cfg;
+#[cfg(feature = "sha256")]
+pub use self::{
+    fixed::{sha256_32bytes, sha256_64bytes},
+    multi::Sha256x8,
+    variant::Sha256Cfg,
+};
 
 // ======================== hasher aliases ====================================
-//                                    Variant         Block  Digest
+// Block, digest and serialised sizes are taken from the variant; see
+// `ShaHasher::PARAMS_OK` for the invariants checked on every instantiation.
 #[cfg(feature = "sha224")]
-pub type Sha224 = ShaHasher<Sha224Cfg, 64, 28>;
+pub type Sha224 = ShaHasher<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
 #[cfg(feature = "sha256")]
-pub type Sha256 = ShaHasher<Sha256Cfg, 64, 32>;
+pub type Sha256 = ShaHasher<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
 #[cfg(feature = "sha384")]
-pub type Sha384 = ShaHasher<Sha384Cfg, 128, 48>;
+pub type Sha384 = ShaHasher<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
 #[cfg(feature = "sha512")]
-pub type Sha512 = ShaHasher<Sha512Cfg, 128, 64>;
+pub type Sha512 = ShaHasher<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
 #[cfg(feature = "sha512_224")]
-pub type Sha512_224 = ShaHasher<Sha512_224Cfg, 128, 28>;
+pub type Sha512_224 =
+    ShaHasher<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
 #[cfg(feature = "sha512_256")]
-pub type Sha512_256 = ShaHasher<Sha512_256Cfg, 128, 32>;
+pub type Sha512_256 =
+    ShaHasher<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
+
+// ====================== tree hasher aliases ================================
+
+#[cfg(all(feature = "std", feature = "sha224"))]
+pub type Sha224Tree =
+    TreeHasher<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
+#[cfg(all(feature = "std", feature = "sha256"))]
+pub type Sha256Tree =
+    TreeHasher<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
+#[cfg(all(feature = "std", feature = "sha384"))]
+pub type Sha384Tree =
+    TreeHasher<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
+#[cfg(all(feature = "std", feature = "sha512"))]
+pub type Sha512Tree =
+    TreeHasher<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
+#[cfg(all(feature = "std", feature = "sha512_224"))]
+pub type Sha512_224Tree =
+    TreeHasher<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
+#[cfg(all(feature = "std", feature = "sha512_256"))]
+pub type Sha512_256Tree =
+    TreeHasher<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
+
+// ======================== HMAC, HKDF and DRBG aliases ========================
+
+#[cfg(feature = "sha224")]
+pub type HmacSha224 = Hmac<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha256")]
+pub type HmacSha256 = Hmac<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha384")]
+pub type HmacSha384 = Hmac<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512")]
+pub type HmacSha512 = Hmac<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_224")]
+pub type HmacSha512_224 =
+    Hmac<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_256")]
+pub type HmacSha512_256 =
+    Hmac<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
+
+#[cfg(feature = "sha224")]
+pub type HkdfSha224 = Hkdf<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha256")]
+pub type HkdfSha256 = Hkdf<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha384")]
+pub type HkdfSha384 = Hkdf<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512")]
+pub type HkdfSha512 = Hkdf<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_224")]
+pub type HkdfSha512_224 =
+    Hkdf<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_256")]
+pub type HkdfSha512_256 =
+    Hkdf<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
+
+#[cfg(feature = "sha224")]
+pub type HmacDrbgSha224 =
+    HmacDrbg<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha256")]
+pub type HmacDrbgSha256 =
+    HmacDrbg<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha384")]
+pub type HmacDrbgSha384 =
+    HmacDrbg<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512")]
+pub type HmacDrbgSha512 =
+    HmacDrbg<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_224")]
+pub type HmacDrbgSha512_224 =
+    HmacDrbg<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_256")]
+pub type HmacDrbgSha512_256 =
+    HmacDrbg<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
+
+#[cfg(feature = "sha224")]
+pub type HashDrbgSha224 =
+    HashDrbg<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha256")]
+pub type HashDrbgSha256 =
+    HashDrbg<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha384")]
+pub type HashDrbgSha384 =
+    HashDrbg<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512")]
+pub type HashDrbgSha512 =
+    HashDrbg<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_224")]
+pub type HashDrbgSha512_224 =
+    HashDrbg<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::DIGEST_BYTES }>;
+#[cfg(feature = "sha512_256")]
+pub type HashDrbgSha512_256 =
+    HashDrbg<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::DIGEST_BYTES }>;
 
 // ==================== serialised-state aliases =============================
-//                                    Variant         Block  Serial
-//                                                   (STATE_BYTES+BLOCK+1+LEN_BYTES)
 
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha224",
 ))]
-pub type Sha224State = ShaState<Sha224Cfg, 64, 105>;
+pub type Sha224State = ShaState<Sha224Cfg, { Sha224Cfg::BLOCK_BYTES }, { Sha224Cfg::SERIAL_BYTES }>;
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha256",
 ))]
-pub type Sha256State = ShaState<Sha256Cfg, 64, 105>;
+pub type Sha256State = ShaState<Sha256Cfg, { Sha256Cfg::BLOCK_BYTES }, { Sha256Cfg::SERIAL_BYTES }>;
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha384",
 ))]
-pub type Sha384State = ShaState<Sha384Cfg, 128, 209>;
+pub type Sha384State = ShaState<Sha384Cfg, { Sha384Cfg::BLOCK_BYTES }, { Sha384Cfg::SERIAL_BYTES }>;
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha512",
 ))]
-pub type Sha512State = ShaState<Sha512Cfg, 128, 209>;
+pub type Sha512State = ShaState<Sha512Cfg, { Sha512Cfg::BLOCK_BYTES }, { Sha512Cfg::SERIAL_BYTES }>;
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha512_224",
 ))]
-pub type Sha512_224State = ShaState<Sha512_224Cfg, 128, 209>;
+pub type Sha512_224State =
+    ShaState<Sha512_224Cfg, { Sha512_224Cfg::BLOCK_BYTES }, { Sha512_224Cfg::SERIAL_BYTES }>;
 #[cfg(all(
     any(feature = "deserialize", feature = "serialize",),
     feature = "sha512_256",
 ))]
-pub type Sha512_256State = ShaState<Sha512_256Cfg, 128, 209>;
+pub type Sha512_256State =
+    ShaState<Sha512_256Cfg, { Sha512_256Cfg::BLOCK_BYTES }, { Sha512_256Cfg::SERIAL_BYTES }>;
+
+/// Keeps [`ShaFamily`] and [`ShaWord`] closed to downstream implementations;
+/// only variants are user-extensible.
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+mod sealed {
+    pub trait Sealed {}
+}
 
 #[cfg(test)]
 mod tests;
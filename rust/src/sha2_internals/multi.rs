@@ -0,0 +1,612 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Multi-buffer SHA-224/256: hashes several independent messages at once,
+//! one message per SIMD lane.
+//!
+//! Every backend runs the same lane driver, which feeds each lane its own
+//! blocks (message, then padding) and collects a lane's digest as soon as
+//! its last block has been compressed. Lanes that finish early keep being
+//! fed their final block; the extra work is discarded.
+
+use super::family::{Sha2_32, ShaFamily};
+#[cfg(feature = "sha256")]
+use super::variant::{Sha256Cfg, ShaVariant};
+
+/// Hashes up to eight independent messages in parallel.
+///
+/// Uses AVX2 (8 lanes) or SSE2 (4 lanes) when the CPU supports them, or
+/// WebAssembly SIMD128 (4 lanes) when built with the `simd128` feature for
+/// a target that enables it, and falls back to the scalar compression
+/// function otherwise. Output is
+/// identical to hashing each message with [`Sha256`](crate::Sha256).
+#[cfg(feature = "sha256")]
+pub struct Sha256x8;
+
+#[cfg(feature = "sha256")]
+impl Sha256x8 {
+    /// Number of messages hashed per AVX2 pass.
+    pub const LANES: usize = 8;
+
+    /// Hash exactly eight messages.
+    pub fn digest(inputs: &[&[u8]; 8]) -> [[u8; 32]; 8] {
+        let mut out = [[0u8; 32]; 8];
+        Self::hash_many(inputs, &mut out);
+        out
+    }
+
+    /// Hash every message in `inputs` into the matching slot of `outputs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` and `outputs` differ in length.
+    pub fn hash_many(inputs: &[&[u8]], outputs: &mut [[u8; 32]]) {
+        assert_eq!(
+            inputs.len(),
+            outputs.len(),
+            "inputs and outputs must have the same length"
+        );
+        hash_many(&Sha256Cfg::IV, inputs, outputs);
+    }
+}
+
+/// Hash every message in `inputs` from the chaining value `iv`, keeping the
+/// first `D` bytes of each final state.
+pub(super) fn hash_many<const D: usize>(iv: &[u32; 8], inputs: &[&[u8]], outputs: &mut [[u8; D]]) {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if let Some(avx2) = x86::Avx2::detect() {
+            return hash_chunks(&avx2, iv, inputs, outputs);
+        }
+        if let Some(sse2) = x86::Sse2::detect() {
+            return hash_chunks(&sse2, iv, inputs, outputs);
+        }
+    }
+
+    #[cfg(all(
+        feature = "simd128",
+        target_arch = "wasm32",
+        target_feature = "simd128"
+    ))]
+    hash_chunks(&wasm::Simd128, iv, inputs, outputs);
+
+    #[cfg(not(all(
+        feature = "simd128",
+        target_arch = "wasm32",
+        target_feature = "simd128"
+    )))]
+    hash_chunks(&Scalar, iv, inputs, outputs);
+}
+
+// ---- lane driver -----------------------------------------------------------
+
+/// A SHA-256 compression backend operating on `N` lanes.
+pub(super) trait Lanes<const N: usize> {
+    type State;
+
+    fn init(&self, iv: &[u32; 8]) -> Self::State;
+    fn compress(&self, state: &mut Self::State, blocks: &[&[u8; 64]; N]);
+    fn extract(&self, state: &Self::State) -> [[u32; 8]; N];
+}
+
+/// The blocks of one message: whole blocks borrowed from the input, then
+/// one or two blocks holding the remainder and the padding.
+struct LaneSource<'a> {
+    msg: &'a [u8],
+    full_blocks: usize,
+    blocks: usize,
+    tail: [u8; 128],
+}
+
+impl<'a> LaneSource<'a> {
+    fn new(msg: &'a [u8]) -> Self {
+        let full_blocks = msg.len() / 64;
+        let rest = &msg[full_blocks * 64..];
+        let mut tail = [0u8; 128];
+        tail[..rest.len()].copy_from_slice(rest);
+        let pad_len = Sha2_32::write_padding(msg.len() as u128, &mut tail[rest.len()..]);
+
+        Self {
+            msg,
+            full_blocks,
+            blocks: full_blocks + (rest.len() + pad_len) / 64,
+            tail,
+        }
+    }
+
+    fn block(&self, j: usize) -> &[u8; 64] {
+        // Finished lanes keep re-reading their final block.
+        let j = j.min(self.blocks - 1);
+        let src = if j < self.full_blocks {
+            &self.msg[j * 64..]
+        } else {
+            &self.tail[(j - self.full_blocks) * 64..]
+        };
+        src.first_chunk().unwrap()
+    }
+}
+
+/// Hash `N` messages on one backend pass.
+pub(super) fn hash_lanes<const N: usize, const D: usize, L: Lanes<N>>(
+    backend: &L,
+    iv: &[u32; 8],
+    inputs: &[&[u8]; N],
+    outputs: &mut [[u8; D]; N],
+) {
+    let lanes: [LaneSource<'_>; N] = ::core::array::from_fn(|i| LaneSource::new(inputs[i]));
+    let max_blocks = lanes.iter().map(|l| l.blocks).max().unwrap_or(0);
+
+    let mut state = backend.init(iv);
+    for j in 0..max_blocks {
+        let blocks: [&[u8; 64]; N] = ::core::array::from_fn(|i| lanes[i].block(j));
+        backend.compress(&mut state, &blocks);
+
+        if lanes.iter().any(|l| l.blocks == j + 1) {
+            let words = backend.extract(&state);
+            for (i, lane) in lanes.iter().enumerate() {
+                if lane.blocks == j + 1 {
+                    let mut full = [0u8; 32];
+                    for (k, w) in words[i].iter().enumerate() {
+                        full[k * 4..k * 4 + 4].copy_from_slice(&w.to_be_bytes());
+                    }
+                    outputs[i].copy_from_slice(&full[..D]);
+                }
+            }
+        }
+    }
+}
+
+/// Hash any number of messages in groups of `N`, padding the last group
+/// with empty messages.
+pub(super) fn hash_chunks<const N: usize, const D: usize, L: Lanes<N>>(
+    backend: &L,
+    iv: &[u32; 8],
+    inputs: &[&[u8]],
+    outputs: &mut [[u8; D]],
+) {
+    for (ins, outs) in inputs.chunks(N).zip(outputs.chunks_mut(N)) {
+        let mut group_in: [&[u8]; N] = [&[]; N];
+        let mut group_out = [[0u8; D]; N];
+        group_in[..ins.len()].copy_from_slice(ins);
+        hash_lanes(backend, iv, &group_in, &mut group_out);
+        outs.copy_from_slice(&group_out[..outs.len()]);
+    }
+}
+
+// ---- scalar reference ------------------------------------------------------
+
+/// One lane on the portable compression function.
+#[cfg_attr(
+    all(
+        feature = "simd128",
+        target_arch = "wasm32",
+        target_feature = "simd128"
+    ),
+    allow(dead_code)
+)]
+pub(super) struct Scalar;
+
+impl Lanes<1> for Scalar {
+    type State = [u32; 8];
+
+    fn init(&self, iv: &[u32; 8]) -> [u32; 8] {
+        *iv
+    }
+
+    fn compress(&self, state: &mut [u32; 8], blocks: &[&[u8; 64]; 1]) {
+        Sha2_32::compress(state, blocks[0]);
+    }
+
+    fn extract(&self, state: &[u32; 8]) -> [[u32; 8]; 1] {
+        [*state]
+    }
+}
+
+// ---- x86 / x86-64 ----------------------------------------------------------
+
+/// Defines `compress` for a lane vector type, given lane-wise helpers
+/// `add`, `xor`, `and`, `or`, `shr::<N>`, `shl::<N>`, `splat` and `load`
+/// in the invoking module, all compiled for `$feature`.
+#[cfg(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(
+        feature = "simd128",
+        target_arch = "wasm32",
+        target_feature = "simd128"
+    )
+))]
+macro_rules! lanes_compress {
+    ($feature:literal, $vec:ty, $lanes:literal) => {
+        #[inline]
+        #[target_feature(enable = $feature)]
+        fn rotr<const R: i32, const L: i32>(x: $vec) -> $vec {
+            or(shr::<R>(x), shl::<L>(x))
+        }
+
+        #[target_feature(enable = $feature)]
+        pub(super) fn compress(state: &mut [$vec; 8], blocks: &[&[u8; 64]; $lanes]) {
+            let mut w = [splat(0); 16];
+            for (t, wt) in w.iter_mut().enumerate() {
+                *wt = load(blocks, t);
+            }
+
+            let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+            for (i, &k) in K32.iter().enumerate() {
+                if i >= 16 {
+                    let w1 = w[(i + 1) & 15];
+                    let w14 = w[(i + 14) & 15];
+                    let s0 = xor(xor(rotr::<7, 25>(w1), rotr::<18, 14>(w1)), shr::<3>(w1));
+                    let s1 = xor(
+                        xor(rotr::<17, 15>(w14), rotr::<19, 13>(w14)),
+                        shr::<10>(w14),
+                    );
+                    w[i & 15] = add(add(w[i & 15], s1), add(w[(i + 9) & 15], s0));
+                }
+
+                let ch = xor(g, and(e, xor(f, g)));
+                let maj = xor(and(a, b), and(c, xor(a, b)));
+                let s1 = xor(xor(rotr::<6, 26>(e), rotr::<11, 21>(e)), rotr::<25, 7>(e));
+                let s0 = xor(xor(rotr::<2, 30>(a), rotr::<13, 19>(a)), rotr::<22, 10>(a));
+                let t1 = add(add(add(h, s1), add(ch, splat(k))), w[i & 15]);
+                let t2 = add(s0, maj);
+
+                h = g;
+                g = f;
+                f = e;
+                e = add(d, t1);
+                d = c;
+                c = b;
+                b = a;
+                a = add(t1, t2);
+            }
+
+            for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+                *s = add(*s, v);
+            }
+        }
+    };
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(super) mod x86 {
+    use super::{super::family::K32, Lanes};
+
+    /// Big-endian message word `t` of `block`.
+    #[inline(always)]
+    fn word(block: &[u8; 64], t: usize) -> i32 {
+        i32::from_be_bytes(*block[t * 4..].first_chunk().unwrap())
+    }
+
+    /// Whether the running CPU supports `$feature`.
+    macro_rules! detected {
+        ($feature:tt) => {{
+            #[cfg(any(feature = "std", test))]
+            {
+                ::std::is_x86_feature_detected!($feature)
+            }
+            #[cfg(not(any(feature = "std", test)))]
+            {
+                cfg!(target_feature = $feature)
+            }
+        }};
+    }
+
+    // ---- SSE2, 4 lanes ----
+
+    mod sse2 {
+        #[cfg(target_arch = "x86")]
+        use ::core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use ::core::arch::x86_64::*;
+
+        use super::{K32, word};
+
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn add(a: __m128i, b: __m128i) -> __m128i {
+            _mm_add_epi32(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn xor(a: __m128i, b: __m128i) -> __m128i {
+            _mm_xor_si128(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn and(a: __m128i, b: __m128i) -> __m128i {
+            _mm_and_si128(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn or(a: __m128i, b: __m128i) -> __m128i {
+            _mm_or_si128(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn shr<const N: i32>(a: __m128i) -> __m128i {
+            _mm_srli_epi32::<N>(a)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn shl<const N: i32>(a: __m128i) -> __m128i {
+            _mm_slli_epi32::<N>(a)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        pub(super) fn splat(x: u32) -> __m128i {
+            _mm_set1_epi32(x as i32)
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        fn load(blocks: &[&[u8; 64]; 4], t: usize) -> __m128i {
+            _mm_setr_epi32(
+                word(blocks[0], t),
+                word(blocks[1], t),
+                word(blocks[2], t),
+                word(blocks[3], t),
+            )
+        }
+        #[inline]
+        #[target_feature(enable = "sse2")]
+        pub(super) fn store(v: __m128i) -> [u32; 4] {
+            let mut out = [0u32; 4];
+            // SAFETY: `out` is 16 bytes; `storeu` has no alignment requirement.
+            unsafe { _mm_storeu_si128(out.as_mut_ptr().cast(), v) };
+            out
+        }
+
+        lanes_compress!("sse2", __m128i, 4);
+    }
+
+    /// Capability token: only constructed once SSE2 is known to be available.
+    pub(in crate::sha2_internals) struct Sse2(());
+
+    impl Sse2 {
+        pub(in crate::sha2_internals) fn detect() -> Option<Self> {
+            detected!("sse2").then_some(Self(()))
+        }
+    }
+
+    #[cfg(target_arch = "x86")]
+    type M128 = ::core::arch::x86::__m128i;
+    #[cfg(target_arch = "x86_64")]
+    type M128 = ::core::arch::x86_64::__m128i;
+
+    impl Lanes<4> for Sse2 {
+        type State = [M128; 8];
+
+        fn init(&self, iv: &[u32; 8]) -> Self::State {
+            // SAFETY: `self` proves SSE2 is available.
+            iv.map(|w| unsafe { sse2::splat(w) })
+        }
+
+        fn compress(&self, state: &mut Self::State, blocks: &[&[u8; 64]; 4]) {
+            // SAFETY: `self` proves SSE2 is available.
+            unsafe { sse2::compress(state, blocks) }
+        }
+
+        fn extract(&self, state: &Self::State) -> [[u32; 8]; 4] {
+            // SAFETY: `self` proves SSE2 is available.
+            let words = state.map(|v| unsafe { sse2::store(v) });
+            ::core::array::from_fn(|lane| ::core::array::from_fn(|k| words[k][lane]))
+        }
+    }
+
+    // ---- AVX2, 8 lanes ----
+
+    mod avx2 {
+        #[cfg(target_arch = "x86")]
+        use ::core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use ::core::arch::x86_64::*;
+
+        use super::{K32, word};
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn add(a: __m256i, b: __m256i) -> __m256i {
+            _mm256_add_epi32(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn xor(a: __m256i, b: __m256i) -> __m256i {
+            _mm256_xor_si256(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn and(a: __m256i, b: __m256i) -> __m256i {
+            _mm256_and_si256(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn or(a: __m256i, b: __m256i) -> __m256i {
+            _mm256_or_si256(a, b)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn shr<const N: i32>(a: __m256i) -> __m256i {
+            _mm256_srli_epi32::<N>(a)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn shl<const N: i32>(a: __m256i) -> __m256i {
+            _mm256_slli_epi32::<N>(a)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub(super) fn splat(x: u32) -> __m256i {
+            _mm256_set1_epi32(x as i32)
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        fn load(blocks: &[&[u8; 64]; 8], t: usize) -> __m256i {
+            _mm256_setr_epi32(
+                word(blocks[0], t),
+                word(blocks[1], t),
+                word(blocks[2], t),
+                word(blocks[3], t),
+                word(blocks[4], t),
+                word(blocks[5], t),
+                word(blocks[6], t),
+                word(blocks[7], t),
+            )
+        }
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub(super) fn store(v: __m256i) -> [u32; 8] {
+            let mut out = [0u32; 8];
+            // SAFETY: `out` is 32 bytes; `storeu` has no alignment requirement.
+            unsafe { _mm256_storeu_si256(out.as_mut_ptr().cast(), v) };
+            out
+        }
+
+        lanes_compress!("avx2", __m256i, 8);
+    }
+
+    /// Capability token: only constructed once AVX2 is known to be available.
+    pub(in crate::sha2_internals) struct Avx2(());
+
+    impl Avx2 {
+        pub(in crate::sha2_internals) fn detect() -> Option<Self> {
+            detected!("avx2").then_some(Self(()))
+        }
+    }
+
+    #[cfg(target_arch = "x86")]
+    type M256 = ::core::arch::x86::__m256i;
+    #[cfg(target_arch = "x86_64")]
+    type M256 = ::core::arch::x86_64::__m256i;
+
+    impl Lanes<8> for Avx2 {
+        type State = [M256; 8];
+
+        fn init(&self, iv: &[u32; 8]) -> Self::State {
+            // SAFETY: `self` proves AVX2 is available.
+            iv.map(|w| unsafe { avx2::splat(w) })
+        }
+
+        fn compress(&self, state: &mut Self::State, blocks: &[&[u8; 64]; 8]) {
+            // SAFETY: `self` proves AVX2 is available.
+            unsafe { avx2::compress(state, blocks) }
+        }
+
+        fn extract(&self, state: &Self::State) -> [[u32; 8]; 8] {
+            // SAFETY: `self` proves AVX2 is available.
+            let words = state.map(|v| unsafe { avx2::store(v) });
+            ::core::array::from_fn(|lane| ::core::array::from_fn(|k| words[k][lane]))
+        }
+    }
+}
+
+// ---- WebAssembly SIMD128 ---------------------------------------------------
+
+#[cfg(all(
+    feature = "simd128",
+    target_arch = "wasm32",
+    target_feature = "simd128"
+))]
+pub(super) mod wasm {
+    use ::core::arch::wasm32::*;
+
+    use super::{super::family::K32, Lanes};
+
+    /// Big-endian message word `t` of `block`.
+    #[inline(always)]
+    fn word(block: &[u8; 64], t: usize) -> u32 {
+        u32::from_be_bytes(*block[t * 4..].first_chunk().unwrap())
+    }
+
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn add(a: v128, b: v128) -> v128 {
+        i32x4_add(a, b)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn xor(a: v128, b: v128) -> v128 {
+        v128_xor(a, b)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn and(a: v128, b: v128) -> v128 {
+        v128_and(a, b)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn or(a: v128, b: v128) -> v128 {
+        v128_or(a, b)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn shr<const N: i32>(a: v128) -> v128 {
+        u32x4_shr(a, N as u32)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn shl<const N: i32>(a: v128) -> v128 {
+        i32x4_shl(a, N as u32)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn splat(x: u32) -> v128 {
+        u32x4_splat(x)
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn load(blocks: &[&[u8; 64]; 4], t: usize) -> v128 {
+        u32x4(
+            word(blocks[0], t),
+            word(blocks[1], t),
+            word(blocks[2], t),
+            word(blocks[3], t),
+        )
+    }
+    #[inline]
+    #[target_feature(enable = "simd128")]
+    fn store(v: v128) -> [u32; 4] {
+        [
+            u32x4_extract_lane::<0>(v),
+            u32x4_extract_lane::<1>(v),
+            u32x4_extract_lane::<2>(v),
+            u32x4_extract_lane::<3>(v),
+        ]
+    }
+
+    lanes_compress!("simd128", v128, 4);
+
+    /// SIMD128 backend. WebAssembly has no runtime detection; this module
+    /// only exists when the target is compiled with `simd128` enabled, so
+    /// the token needs no proof of availability.
+    pub(in crate::sha2_internals) struct Simd128;
+
+    impl Lanes<4> for Simd128 {
+        type State = [v128; 8];
+
+        fn init(&self, iv: &[u32; 8]) -> Self::State {
+            iv.map(|w| splat(w))
+        }
+
+        fn compress(&self, state: &mut Self::State, blocks: &[&[u8; 64]; 4]) {
+            compress(state, blocks)
+        }
+
+        fn extract(&self, state: &Self::State) -> [[u32; 8]; 4] {
+            let words = state.map(|v| store(v));
+            ::core::array::from_fn(|lane| ::core::array::from_fn(|k| words[k][lane]))
+        }
+    }
+}
@@ -0,0 +1,242 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! The hash-dependent parts of the Noise Protocol Framework (revision 34,
+//! sections 4 and 5): `HKDF`, the `SymmetricState` and `Split`, for the
+//! `SHA256` and `SHA512` hash names.
+//!
+//! The handshake code supplies the DH and the AEAD. [`CipherState`]
+//! only tracks the key and nonce, so `EncryptAndHash` becomes
+//!
+//! ```
+//! # #[cfg(feature = "sha256")] {
+//! # use sha2::{noise::SymmetricState, Sha256Cfg};
+//! # let mut state = SymmetricState::<Sha256Cfg, 64, 32>::new(b"Noise_NN_25519_ChaChaPoly_SHA256");
+//! # let seal = |_k: &[u8; 32], _n: u64, _ad: &[u8], p: &[u8]| p.to_vec();
+//! # let plaintext = b"payload";
+//! let ad = *state.handshake_hash();
+//! let ciphertext = match state.cipher().key().copied() {
+//!     Some(k) => seal(&k, state.cipher_mut().next_nonce().unwrap(), &ad, plaintext),
+//!     None => plaintext.to_vec(),
+//! };
+//! state.mix_hash(&ciphertext);
+//! # }
+//! ```
+
+use super::{hasher::ShaHasher, hmac::Hmac, variant::ShaVariant};
+
+/// Length of a cipher key; longer HKDF outputs are truncated to it.
+pub const KEY_LEN: usize = 32;
+
+/// `HKDF(chaining_key, input_key_material, 2)`.
+pub fn hkdf2<V: ShaVariant, const B: usize, const D: usize>(
+    chaining_key: &[u8; D],
+    ikm: &[u8],
+) -> ([u8; D], [u8; D]) {
+    let [a, b] = hkdf::<V, B, D, 2>(chaining_key, ikm);
+    (a, b)
+}
+
+/// `HKDF(chaining_key, input_key_material, 3)`.
+pub fn hkdf3<V: ShaVariant, const B: usize, const D: usize>(
+    chaining_key: &[u8; D],
+    ikm: &[u8],
+) -> ([u8; D], [u8; D], [u8; D]) {
+    let [a, b, c] = hkdf::<V, B, D, 3>(chaining_key, ikm);
+    (a, b, c)
+}
+
+/// `output_i = HMAC(temp_key, output_{i-1} || i)` with
+/// `temp_key = HMAC(chaining_key, ikm)`.
+fn hkdf<V: ShaVariant, const B: usize, const D: usize, const N: usize>(
+    chaining_key: &[u8; D],
+    ikm: &[u8],
+) -> [[u8; D]; N] {
+    let mut mac = Hmac::<V, B, D>::new(&Hmac::<V, B, D>::mac(chaining_key, ikm));
+    let mut out = [[0u8; D]; N];
+    for i in 0..N {
+        if i > 0 {
+            let prev = out[i - 1];
+            mac.update(&prev);
+        }
+        mac.update(&[i as u8 + 1]);
+        out[i] = mac.finalize();
+    }
+    out
+}
+
+fn truncate<const D: usize>(k: &[u8; D]) -> [u8; KEY_LEN] {
+    ::core::array::from_fn(|i| k[i])
+}
+
+/// A `CipherState`: the key `k`, if any, and the nonce `n`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CipherState {
+    k: Option<[u8; KEY_LEN]>,
+    n: u64,
+}
+
+impl CipherState {
+    /// `InitializeKey(key)`: set the key and reset the nonce.
+    pub fn new(key: Option<[u8; KEY_LEN]>) -> Self {
+        Self { k: key, n: 0 }
+    }
+
+    /// The key, or `None` while data passes in the clear.
+    pub fn key(&self) -> Option<&[u8; KEY_LEN]> {
+        self.k.as_ref()
+    }
+
+    /// Replace the key and keep the nonce, as after `Rekey()`.
+    pub fn set_key(&mut self, key: [u8; KEY_LEN]) {
+        self.k = Some(key);
+    }
+
+    /// The nonce the next message will use.
+    pub fn nonce(&self) -> u64 {
+        self.n
+    }
+
+    /// `SetNonce(nonce)`, for out-of-order transports.
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.n = nonce;
+    }
+
+    /// The nonce for the next AEAD call, advancing the counter.
+    ///
+    /// Fails once `2^64 - 1`, which Noise reserves, is reached; the key
+    /// must not be used any further.
+    pub fn next_nonce(&mut self) -> Result<u64, &'static str> {
+        if self.n == u64::MAX {
+            return Err("nonce exhausted");
+        }
+        self.n += 1;
+        Ok(self.n - 1)
+    }
+}
+
+/// The 96-bit nonce of the `ChaChaPoly` cipher: 32 zero bits, then `n`
+/// little-endian.
+pub fn chacha_poly_nonce(n: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&n.to_le_bytes());
+    nonce
+}
+
+/// The 96-bit nonce of the `AESGCM` cipher: 32 zero bits, then `n`
+/// big-endian.
+pub fn aes_gcm_nonce(n: u64) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&n.to_be_bytes());
+    nonce
+}
+
+/// A `SymmetricState`: the chaining key `ck`, the handshake hash `h` and
+/// the handshake [`CipherState`].
+///
+/// The digest must be at least [`KEY_LEN`] bytes, as it is for the two
+/// hashes Noise defines over SHA-2.
+pub struct SymmetricState<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    ck: [u8; DIGEST],
+    h: [u8; DIGEST],
+    cipher: CipherState,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for SymmetricState<V, B, D> {
+    fn clone(&self) -> Self {
+        Self {
+            ck: self.ck,
+            h: self.h,
+            cipher: self.cipher.clone(),
+            _variant: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> SymmetricState<V, B, D> {
+    /// `InitializeSymmetric(protocol_name)`.
+    pub fn new(protocol_name: &[u8]) -> Self {
+        const { assert!(D >= KEY_LEN, "Noise needs a digest of at least 32 bytes") };
+        let mut h = [0u8; D];
+        if protocol_name.len() <= D {
+            h[..protocol_name.len()].copy_from_slice(protocol_name);
+        } else {
+            h = ShaHasher::<V, B, D>::new().finalize_with(protocol_name);
+        }
+        Self {
+            ck: h,
+            h,
+            cipher: CipherState::default(),
+            _variant: ::core::marker::PhantomData,
+        }
+    }
+
+    /// `MixKey(input_key_material)`, e.g. with a DH output.
+    pub fn mix_key(&mut self, ikm: &[u8]) {
+        let (ck, temp_k) = hkdf2::<V, B, D>(&self.ck, ikm);
+        self.ck = ck;
+        self.cipher = CipherState::new(Some(truncate(&temp_k)));
+    }
+
+    /// `MixHash(data)`: `h = HASH(h || data)`.
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        let mut hasher = ShaHasher::<V, B, D>::new();
+        hasher.update(&self.h);
+        self.h = hasher.finalize_with(data);
+    }
+
+    /// `MixKeyAndHash(input_key_material)`, used for pre-shared keys.
+    pub fn mix_key_and_hash(&mut self, ikm: &[u8]) {
+        let (ck, temp_h, temp_k) = hkdf3::<V, B, D>(&self.ck, ikm);
+        self.ck = ck;
+        self.mix_hash(&temp_h);
+        self.cipher = CipherState::new(Some(truncate(&temp_k)));
+    }
+
+    /// `GetHandshakeHash()`, the associated data of handshake messages and
+    /// the channel binding value once the handshake is done.
+    pub fn handshake_hash(&self) -> &[u8; D] {
+        &self.h
+    }
+
+    /// The chaining key `ck`, which [`split`](Self::split) derives the
+    /// transport keys from. Secret.
+    pub fn chaining_key(&self) -> &[u8; D] {
+        &self.ck
+    }
+
+    /// The handshake cipher state.
+    pub fn cipher(&self) -> &CipherState {
+        &self.cipher
+    }
+
+    /// The handshake cipher state, to take nonces from with
+    /// [`CipherState::next_nonce`] in `EncryptAndHash` and
+    /// `DecryptAndHash`.
+    pub fn cipher_mut(&mut self) -> &mut CipherState {
+        &mut self.cipher
+    }
+
+    /// `Split()`: the cipher states for initiator-to-responder and
+    /// responder-to-initiator transport messages.
+    pub fn split(&self) -> (CipherState, CipherState) {
+        let (k1, k2) = hkdf2::<V, B, D>(&self.ck, &[]);
+        (
+            CipherState::new(Some(truncate(&k1))),
+            CipherState::new(Some(truncate(&k2))),
+        )
+    }
+}
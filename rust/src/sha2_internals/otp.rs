@@ -0,0 +1,477 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! One-time passwords: HOTP (RFC 4226) and TOTP (RFC 6238) with HMAC
+//! over a SHA-2 variant, and `otpauth://` URIs as used by authenticator
+//! apps.
+//!
+//! Time is a Unix timestamp in seconds supplied by the caller. SHA-1,
+//! the URI default, is not available in this crate.
+
+use super::{hmac::Hmac, hmac::ct_eq, variant::ShaVariant};
+
+#[cfg(feature = "sha256")]
+use super::variant::Sha256Cfg;
+#[cfg(feature = "sha512")]
+use super::variant::Sha512Cfg;
+
+/// Fewest digits in a code.
+pub const MIN_DIGITS: u32 = 6;
+/// Most digits in a code; the truncated HMAC has 31 bits.
+pub const MAX_DIGITS: u32 = 10;
+
+/// A one-time password, zero-padded to its number of digits.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Code {
+    buf: [u8; MAX_DIGITS as usize],
+    len: u8,
+}
+
+impl Code {
+    fn new(value: u32, digits: u32) -> Self {
+        let mut buf = [b'0'; MAX_DIGITS as usize];
+        let mut value = value;
+        for b in buf[..digits as usize].iter_mut().rev() {
+            *b = b'0' + (value % 10) as u8;
+            value /= 10;
+        }
+        Self {
+            buf,
+            len: digits as u8,
+        }
+    }
+
+    /// The code as typed by the user, with its leading zeros.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits are ever written.
+        ::core::str::from_utf8(&self.buf[..self.len as usize]).unwrap_or_default()
+    }
+
+    /// The code as a number, without its leading zeros.
+    pub fn value(&self) -> u64 {
+        self.buf[..self.len as usize]
+            .iter()
+            .fold(0, |acc, b| acc * 10 + (b - b'0') as u64)
+    }
+
+    /// Compare with a user-entered code in constant time.
+    pub fn matches(&self, code: &str) -> bool {
+        ct_eq(self.as_str().as_bytes(), code.as_bytes())
+    }
+}
+
+impl ::core::fmt::Display for Code {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ::core::fmt::Debug for Code {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        ::core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+fn check_digits(digits: u32) -> Result<(), &'static str> {
+    if !(MIN_DIGITS..=MAX_DIGITS).contains(&digits) {
+        return Err("digits must be between 6 and 10");
+    }
+    Ok(())
+}
+
+// ---- HOTP ------------------------------------------------------------------
+
+/// HOTP (RFC 4226, section 5.3): the `digits`-digit code for `counter`.
+pub fn hotp<V: ShaVariant, const B: usize, const D: usize>(
+    key: &[u8],
+    counter: u64,
+    digits: u32,
+) -> Result<Code, &'static str> {
+    check_digits(digits)?;
+    Ok(hotp_unchecked(&Hmac::<V, B, D>::new(key), counter, digits))
+}
+
+fn hotp_unchecked<V: ShaVariant, const B: usize, const D: usize>(
+    mac: &Hmac<V, B, D>,
+    counter: u64,
+    digits: u32,
+) -> Code {
+    let mut mac = mac.clone();
+    mac.update(&counter.to_be_bytes());
+    let h = mac.finalize();
+    // Dynamic truncation.
+    let offset = (h[D - 1] & 0x0f) as usize;
+    let bin = u32::from_be_bytes([h[offset], h[offset + 1], h[offset + 2], h[offset + 3]]);
+    let bin = bin & 0x7fff_ffff;
+    Code::new((bin as u64 % 10u64.pow(digits)) as u32, digits)
+}
+
+/// Check `code` against the counters `counter..=counter + look_ahead`.
+///
+/// Returns the matching counter; the caller resynchronises to one past
+/// it. Every counter in the window is tried, so the time taken does not
+/// reveal which one matched.
+pub fn hotp_verify<V: ShaVariant, const B: usize, const D: usize>(
+    key: &[u8],
+    counter: u64,
+    look_ahead: u64,
+    digits: u32,
+    code: &str,
+) -> Result<Option<u64>, &'static str> {
+    check_digits(digits)?;
+    let last = counter.checked_add(look_ahead).ok_or("counter overflow")?;
+    let mac = Hmac::<V, B, D>::new(key);
+    Ok(find(counter..=last, |c| {
+        hotp_unchecked(&mac, c, digits).matches(code)
+    }))
+}
+
+/// The first value in `range` for which `matches` holds, calling it for
+/// every value.
+fn find(
+    range: ::core::ops::RangeInclusive<u64>,
+    mut matches: impl FnMut(u64) -> bool,
+) -> Option<u64> {
+    let mut found = None;
+    for c in range {
+        if matches(c) && found.is_none() {
+            found = Some(c);
+        }
+    }
+    found
+}
+
+// ---- TOTP ------------------------------------------------------------------
+
+/// TOTP parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TotpParams {
+    /// Time step `X` in seconds. Must be non-zero.
+    pub step: u64,
+    /// Unix time `T0` at which counting starts.
+    pub t0: u64,
+    /// Code length, 6 to 10.
+    pub digits: u32,
+    /// Steps either side of the current one accepted by
+    /// [`totp_verify`], for clock drift and slow typing.
+    pub skew: u64,
+}
+
+impl Default for TotpParams {
+    /// 30-second steps from the epoch, six digits, one step of skew.
+    fn default() -> Self {
+        Self {
+            step: 30,
+            t0: 0,
+            digits: 6,
+            skew: 1,
+        }
+    }
+}
+
+impl TotpParams {
+    fn check(&self) -> Result<(), &'static str> {
+        if self.step == 0 {
+            return Err("step must be non-zero");
+        }
+        check_digits(self.digits)
+    }
+
+    /// The time-step counter `T` at Unix time `time`.
+    pub fn counter(&self, time: u64) -> Result<u64, &'static str> {
+        self.check()?;
+        let elapsed = time.checked_sub(self.t0).ok_or("time before T0")?;
+        Ok(elapsed / self.step)
+    }
+}
+
+/// TOTP (RFC 6238, section 4): the code at Unix time `time`.
+pub fn totp<V: ShaVariant, const B: usize, const D: usize>(
+    key: &[u8],
+    params: TotpParams,
+    time: u64,
+) -> Result<Code, &'static str> {
+    let counter = params.counter(time)?;
+    Ok(hotp_unchecked(
+        &Hmac::<V, B, D>::new(key),
+        counter,
+        params.digits,
+    ))
+}
+
+/// Check `code` at Unix time `time`, accepting `params.skew` steps either
+/// side.
+///
+/// Returns the matching time-step counter. To stop a code being used
+/// twice, the caller records it and rejects any counter not above the
+/// last one accepted.
+pub fn totp_verify<V: ShaVariant, const B: usize, const D: usize>(
+    key: &[u8],
+    params: TotpParams,
+    time: u64,
+    code: &str,
+) -> Result<Option<u64>, &'static str> {
+    let counter = params.counter(time)?;
+    let first = counter.saturating_sub(params.skew);
+    let last = counter.saturating_add(params.skew);
+    let mac = Hmac::<V, B, D>::new(key);
+    Ok(find(first..=last, |c| {
+        hotp_unchecked(&mac, c, params.digits).matches(code)
+    }))
+}
+
+// ---- otpauth:// URIs -------------------------------------------------------
+
+/// The HMAC hash named by a URI's `algorithm` parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    /// `SHA256`.
+    #[cfg(feature = "sha256")]
+    Sha256,
+    /// `SHA512`.
+    #[cfg(feature = "sha512")]
+    Sha512,
+}
+
+impl Algorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        #[cfg(feature = "sha256")]
+        if name.eq_ignore_ascii_case("SHA256") {
+            return Some(Self::Sha256);
+        }
+        #[cfg(feature = "sha512")]
+        if name.eq_ignore_ascii_case("SHA512") {
+            return Some(Self::Sha512);
+        }
+        None
+    }
+
+    /// [`hotp`] with this hash.
+    pub fn hotp(self, key: &[u8], counter: u64, digits: u32) -> Result<Code, &'static str> {
+        match self {
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => hotp::<Sha256Cfg, 64, 32>(key, counter, digits),
+            #[cfg(feature = "sha512")]
+            Self::Sha512 => hotp::<Sha512Cfg, 128, 64>(key, counter, digits),
+        }
+    }
+}
+
+/// Counter-based or time-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtpKind {
+    /// `otpauth://hotp/`, starting from `counter`.
+    Hotp { counter: u64 },
+    /// `otpauth://totp/`, with `period`-second steps.
+    Totp { period: u64 },
+}
+
+/// A parsed `otpauth://` URI, in the format of the Google Authenticator
+/// key URI specification.
+///
+/// `label` and `issuer` are kept as they appear in the URI, still
+/// percent-encoded; [`label_decoded`](Self::label_decoded) and
+/// [`issuer_decoded`](Self::issuer_decoded) decode them into a caller
+/// buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OtpAuth<'a> {
+    /// HOTP with its starting counter, or TOTP with its period.
+    pub kind: OtpKind,
+    /// `issuer:account` or `account`.
+    pub label: &'a str,
+    /// The `issuer` parameter, if present.
+    pub issuer: Option<&'a str>,
+    /// The unpadded base-32 `secret`; see [`secret`](Self::secret).
+    pub secret_b32: &'a str,
+    /// The HMAC hash from the `algorithm` parameter.
+    pub algorithm: Algorithm,
+    /// Code length from the `digits` parameter, 6 if absent.
+    pub digits: u32,
+}
+
+impl<'a> OtpAuth<'a> {
+    /// Parse an `otpauth://` URI.
+    ///
+    /// Fails on a missing or malformed `secret`, an `algorithm` other than
+    /// SHA-256 or SHA-512 (including the implied SHA-1), out-of-range
+    /// `digits`, a zero `period` or a HOTP URI without a `counter`.
+    /// Unknown parameters are ignored.
+    pub fn parse(uri: &'a str) -> Result<Self, &'static str> {
+        let rest = uri.strip_prefix("otpauth://").ok_or("not an otpauth URI")?;
+        let (kind, rest) = rest.split_once('/').ok_or("not an otpauth URI")?;
+        let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let mut secret_b32 = None;
+        let mut issuer = None;
+        let mut algorithm = None;
+        let mut digits = None;
+        let mut counter = None;
+        let mut period = None;
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "secret" => secret_b32 = Some(value),
+                "issuer" => issuer = Some(value),
+                "algorithm" => {
+                    algorithm = Some(Algorithm::from_name(value).ok_or("unsupported algorithm")?)
+                }
+                "digits" => digits = Some(parse_u64(value)?),
+                "counter" => counter = Some(parse_u64(value)?),
+                "period" => period = Some(parse_u64(value)?),
+                _ => {}
+            }
+        }
+
+        let kind = match kind {
+            "hotp" => OtpKind::Hotp {
+                counter: counter.ok_or("missing counter")?,
+            },
+            "totp" => {
+                let period = period.unwrap_or(30);
+                if period == 0 {
+                    return Err("period must be non-zero");
+                }
+                OtpKind::Totp { period }
+            }
+            _ => return Err("unknown OTP type"),
+        };
+        let secret_b32 = secret_b32.ok_or("missing secret")?;
+        base32_decode(secret_b32, &mut [])?;
+        let digits = u32::try_from(digits.unwrap_or(6)).unwrap_or(u32::MAX);
+        check_digits(digits)?;
+
+        Ok(Self {
+            kind,
+            label,
+            issuer,
+            secret_b32: secret_b32.trim_end_matches('='),
+            algorithm: algorithm.ok_or("unsupported algorithm")?,
+            digits,
+        })
+    }
+
+    /// Percent-decode the label into `out`.
+    pub fn label_decoded<'o>(&self, out: &'o mut [u8]) -> Result<&'o str, &'static str> {
+        percent_decode(self.label, out)
+    }
+
+    /// Percent-decode the `issuer` parameter into `out`.
+    pub fn issuer_decoded<'o>(&self, out: &'o mut [u8]) -> Result<Option<&'o str>, &'static str> {
+        self.issuer
+            .map(|issuer| percent_decode(issuer, out))
+            .transpose()
+    }
+
+    /// Length in bytes of the decoded secret.
+    pub fn secret_len(&self) -> usize {
+        self.secret_b32.len() * 5 / 8
+    }
+
+    /// Decode the secret into `out`, returning the filled prefix.
+    pub fn secret<'o>(&self, out: &'o mut [u8]) -> Result<&'o [u8], &'static str> {
+        let out = out
+            .get_mut(..self.secret_len())
+            .ok_or("output buffer too small")?;
+        base32_decode(self.secret_b32, out)?;
+        Ok(out)
+    }
+
+    /// TOTP parameters for a time-based URI, with `skew` steps of
+    /// tolerance.
+    pub fn totp_params(&self, skew: u64) -> Option<TotpParams> {
+        match self.kind {
+            OtpKind::Totp { period } => Some(TotpParams {
+                step: period,
+                t0: 0,
+                digits: self.digits,
+                skew,
+            }),
+            OtpKind::Hotp { .. } => None,
+        }
+    }
+
+    /// The current code for `key`: at Unix time `time` for TOTP, at the
+    /// URI's counter for HOTP.
+    pub fn code(&self, key: &[u8], time: u64) -> Result<Code, &'static str> {
+        let counter = match self.kind {
+            OtpKind::Hotp { counter } => counter,
+            OtpKind::Totp { period } => time / period,
+        };
+        self.algorithm.hotp(key, counter, self.digits)
+    }
+}
+
+/// Decode `%XX` escapes in `s` into `out`, returning the decoded text.
+/// The result is never longer than `s`.
+///
+/// Fails on malformed escapes, a short `out` or output that is not UTF-8.
+pub fn percent_decode<'o>(s: &str, out: &'o mut [u8]) -> Result<&'o str, &'static str> {
+    let mut bytes = s.bytes();
+    let mut len = 0;
+    while let Some(b) = bytes.next() {
+        let b = if b == b'%' {
+            let hi = bytes.next().and_then(|c| (c as char).to_digit(16));
+            let lo = bytes.next().and_then(|c| (c as char).to_digit(16));
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => (hi << 4 | lo) as u8,
+                _ => return Err("invalid percent-encoding"),
+            }
+        } else {
+            b
+        };
+        *out.get_mut(len).ok_or("output buffer too small")? = b;
+        len += 1;
+    }
+    ::core::str::from_utf8(&out[..len]).map_err(|_| "decoded text is not UTF-8")
+}
+
+fn parse_u64(s: &str) -> Result<u64, &'static str> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("invalid number");
+    }
+    s.parse().map_err(|_| "invalid number")
+}
+
+/// Decode RFC 4648 base 32, case-insensitively and with optional
+/// padding, into as much of `out` as fits. Returns the decoded length.
+fn base32_decode(s: &str, out: &mut [u8]) -> Result<usize, &'static str> {
+    let s = s.trim_end_matches('=');
+    let mut acc = 0u64;
+    let mut bits = 0;
+    let mut len = 0;
+    for b in s.bytes() {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a',
+            b'2'..=b'7' => b - b'2' + 26,
+            _ => return Err("invalid base32 secret"),
+        };
+        acc = (acc << 5) | v as u64;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            if len < out.len() {
+                out[len] = (acc >> bits) as u8;
+            }
+            len += 1;
+        }
+    }
+    // Leftover bits must be padding zeros, and only a whole number of
+    // bytes can leave fewer than five.
+    if acc & ((1 << bits) - 1) != 0 || matches!(s.len() % 8, 1 | 3 | 6) {
+        return Err("invalid base32 secret");
+    }
+    Ok(len)
+}
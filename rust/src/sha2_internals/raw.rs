@@ -0,0 +1,107 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Raw SHA-2 building blocks: the compression functions, round constants,
+//! initial hash values and Merkle–Damgård padding.
+//!
+//! These are the primitives the streaming hashers are built from,
+//! exposed for custom constructions. No buffering or length accounting is
+//! done here; callers are responsible for feeding whole blocks.
+
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+use super::family::Sha2_32;
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+use super::family::Sha2_64;
+use super::{family::ShaFamily, variant::ShaVariant};
+
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+pub use super::family::K32 as K256;
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub use super::family::K64 as K512;
+
+// ---- compression functions -------------------------------------------------
+
+/// Compress one 64-byte block into a SHA-224/256 chaining state.
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+#[inline]
+pub fn compress256(state: &mut [u32; 8], block: &[u8; 64]) {
+    Sha2_32::compress(state, block);
+}
+
+/// Compress one 128-byte block into a SHA-384/512 chaining state.
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+#[inline]
+pub fn compress512(state: &mut [u64; 8], block: &[u8; 128]) {
+    Sha2_64::compress(state, block);
+}
+
+// ---- padding ---------------------------------------------------------------
+
+/// Write the SHA-224/256 padding for a `message_len`-byte message into
+/// `out`, returning its length (9 to 72 bytes).
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than the padding.
+#[cfg(any(feature = "sha224", feature = "sha256"))]
+pub fn pad256(message_len: u64, out: &mut [u8]) -> usize {
+    Sha2_32::write_padding(message_len as u128, out)
+}
+
+/// Write the SHA-384/512 padding for a `message_len`-byte message into
+/// `out`, returning its length (17 to 144 bytes).
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than the padding.
+#[cfg(any(
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+pub fn pad512(message_len: u128, out: &mut [u8]) -> usize {
+    Sha2_64::write_padding(message_len, out)
+}
+
+// ---- initial hash values ---------------------------------------------------
+
+#[cfg(feature = "sha224")]
+pub const SHA224_IV: [u32; 8] = <super::variant::Sha224Cfg as ShaVariant>::IV;
+#[cfg(feature = "sha256")]
+pub const SHA256_IV: [u32; 8] = <super::variant::Sha256Cfg as ShaVariant>::IV;
+#[cfg(feature = "sha384")]
+pub const SHA384_IV: [u64; 8] = <super::variant::Sha384Cfg as ShaVariant>::IV;
+#[cfg(feature = "sha512")]
+pub const SHA512_IV: [u64; 8] = <super::variant::Sha512Cfg as ShaVariant>::IV;
+#[cfg(feature = "sha512_224")]
+pub const SHA512_224_IV: [u64; 8] = <super::variant::Sha512_224Cfg as ShaVariant>::IV;
+#[cfg(feature = "sha512_256")]
+pub const SHA512_256_IV: [u64; 8] = <super::variant::Sha512_256Cfg as ShaVariant>::IV;
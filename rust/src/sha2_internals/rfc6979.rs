@@ -0,0 +1,201 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Deterministic DSA and ECDSA nonces (RFC 6979, section 3.2).
+//!
+//! Integers are unsigned big-endian byte strings; leading zero bytes are
+//! ignored. The nonce `k` is written as exactly `rlen` bytes, the length
+//! of `q` without leading zeros. The HMAC is fixed by the variant, e.g.
+//!
+//! ```
+//! # #[cfg(all(feature = "sha256", feature = "sync"))] {
+//! use sha2::{Sha256, Sha256Cfg, rfc6979};
+//!
+//! # let (x, q) = ([0x01; 32], [0xff; 32]);
+//! let h1 = Sha256::digest(b"sample");
+//! let mut k = [0u8; 32];
+//! rfc6979::generate_k::<Sha256Cfg, 64, 32>(&x, &q, &h1, &mut k).unwrap();
+//! # }
+//! ```
+
+use super::{hmac::Hmac, variant::ShaVariant};
+
+/// Longest supported `q` in bytes, enough for P-521.
+pub const MAX_Q_BYTES: usize = 66;
+
+/// The first nonce candidate in `[1, q - 1]` for private key `x` and
+/// message hash `h1`.
+///
+/// Fails if `q` is below 2 or longer than [`MAX_Q_BYTES`], if `x` is not
+/// in `[1, q - 1]`, or if `k` is not `rlen` bytes long.
+pub fn generate_k<V: ShaVariant, const B: usize, const D: usize>(
+    x: &[u8],
+    q: &[u8],
+    h1: &[u8],
+    k: &mut [u8],
+) -> Result<(), &'static str> {
+    NonceGenerator::<V, B, D>::new(x, q, h1)?.next_k(k)
+}
+
+/// The sequence of nonce candidates of RFC 6979, for signers that must
+/// retry when a nonce yields `r = 0` or `s = 0`.
+pub struct NonceGenerator<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    q: [u8; MAX_Q_BYTES],
+    rlen: usize,
+    qlen: usize,
+    k: [u8; DIGEST],
+    v: [u8; DIGEST],
+    started: bool,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> NonceGenerator<V, B, D> {
+    /// Seed the generator (steps a to g).
+    pub fn new(x: &[u8], q: &[u8], h1: &[u8]) -> Result<Self, &'static str> {
+        let q = strip(q);
+        let rlen = q.len();
+        if rlen > MAX_Q_BYTES {
+            return Err("q is too long");
+        }
+        if rlen == 0 || (rlen == 1 && q[0] < 2) {
+            return Err("q must be at least 2");
+        }
+
+        let mut nonces = Self {
+            q: [0; MAX_Q_BYTES],
+            rlen,
+            qlen: rlen * 8 - q[0].leading_zeros() as usize,
+            k: [0x00; D],
+            v: [0x01; D],
+            started: false,
+            _variant: ::core::marker::PhantomData,
+        };
+        nonces.q[..rlen].copy_from_slice(q);
+
+        let x = strip(x);
+        if x.is_empty() || x.len() > rlen {
+            return Err("x must be in [1, q - 1]");
+        }
+        let mut x_octets = [0u8; MAX_Q_BYTES];
+        x_octets[rlen - x.len()..rlen].copy_from_slice(x);
+        if !nonces.lt_q(&x_octets) {
+            return Err("x must be in [1, q - 1]");
+        }
+
+        // bits2octets(h1): bits2int, then reduce once modulo q.
+        let mut h_octets = nonces.bits2int(h1);
+        if !nonces.lt_q(&h_octets) {
+            sub(&mut h_octets[..rlen], &nonces.q[..rlen]);
+        }
+
+        for sep in [0x00u8, 0x01] {
+            let mut mac = Hmac::<V, B, D>::new(&nonces.k);
+            mac.update(&nonces.v);
+            mac.update(&[sep]);
+            mac.update(&x_octets[..rlen]);
+            mac.update(&h_octets[..rlen]);
+            nonces.k = mac.finalize();
+            nonces.v = Hmac::<V, B, D>::mac(&nonces.k, &nonces.v);
+        }
+        Ok(nonces)
+    }
+
+    /// Length of `q`, and of each nonce, in bytes.
+    pub fn rlen(&self) -> usize {
+        self.rlen
+    }
+
+    /// Write the next candidate in `[1, q - 1]` to `k` (step h).
+    ///
+    /// Fails if `k` is not [`rlen`](Self::rlen) bytes long.
+    pub fn next_k(&mut self, k: &mut [u8]) -> Result<(), &'static str> {
+        if k.len() != self.rlen {
+            return Err("k must be rlen bytes long");
+        }
+        loop {
+            if self.started {
+                let mut mac = Hmac::<V, B, D>::new(&self.k);
+                mac.update(&self.v);
+                mac.update(&[0x00]);
+                self.k = mac.finalize();
+                self.v = Hmac::<V, B, D>::mac(&self.k, &self.v);
+            }
+            self.started = true;
+
+            let mut t = [0u8; MAX_Q_BYTES];
+            for chunk in t[..self.rlen].chunks_mut(D) {
+                self.v = Hmac::<V, B, D>::mac(&self.k, &self.v);
+                chunk.copy_from_slice(&self.v[..chunk.len()]);
+            }
+            let candidate = self.bits2int(&t[..self.rlen]);
+            let nonzero = candidate.iter().fold(0, |acc, &b| acc | b) != 0;
+            if nonzero & self.lt_q(&candidate) {
+                k.copy_from_slice(&candidate[..self.rlen]);
+                return Ok(());
+            }
+        }
+    }
+
+    /// The leftmost `qlen` bits of `bits` as an `rlen`-byte integer.
+    fn bits2int(&self, bits: &[u8]) -> [u8; MAX_Q_BYTES] {
+        let mut out = [0u8; MAX_Q_BYTES];
+        if bits.len() < self.rlen {
+            out[self.rlen - bits.len()..self.rlen].copy_from_slice(bits);
+        } else {
+            out[..self.rlen].copy_from_slice(&bits[..self.rlen]);
+            shr(&mut out[..self.rlen], self.rlen * 8 - self.qlen);
+        }
+        out
+    }
+
+    /// Whether an `rlen`-byte integer is below `q`: the borrow out of
+    /// `a - q`, taken over every byte so the time does not depend on the
+    /// secret `x` or `k`.
+    fn lt_q(&self, a: &[u8; MAX_Q_BYTES]) -> bool {
+        let mut borrow = 0u16;
+        for (&a, &q) in a[..self.rlen].iter().zip(&self.q[..self.rlen]).rev() {
+            borrow = (u16::from(a).wrapping_sub(u16::from(q) + borrow) >> 8) & 1;
+        }
+        ::core::hint::black_box(borrow) == 1
+    }
+}
+
+fn strip(n: &[u8]) -> &[u8] {
+    let zeros = n.iter().take_while(|&&b| b == 0).count();
+    &n[zeros..]
+}
+
+/// `a >>= bits` for `bits < 8`.
+fn shr(a: &mut [u8], bits: usize) {
+    if bits == 0 {
+        return;
+    }
+    let mut carry = 0u8;
+    for byte in a.iter_mut() {
+        let next = *byte << (8 - bits);
+        *byte = (*byte >> bits) | carry;
+        carry = next;
+    }
+}
+
+/// `a -= b` for equal-length integers with `a >= b`.
+fn sub(a: &mut [u8], b: &[u8]) {
+    let mut borrow = 0i16;
+    for (x, &y) in a.iter_mut().zip(b).rev() {
+        let diff = *x as i16 - y as i16 - borrow;
+        *x = diff as u8;
+        borrow = (diff < 0) as i16;
+    }
+}
@@ -0,0 +1,295 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Byte-level RSA encodings from PKCS #1 v2.2 (RFC 8017): MGF1,
+//! EMSA-PSS, EME-OAEP and EMSA-PKCS1-v1_5.
+//!
+//! Only the encoding steps live here; the caller does the modular
+//! exponentiation and supplies any randomness (PSS salts, OAEP seeds).
+//! The encoded message `em` is the caller's slice: `emLen` bytes for
+//! signatures, the modulus length `k` for OAEP. The same variant serves
+//! as message hash and MGF1 hash.
+
+use super::{hasher::ShaHasher, hmac::ct_eq, variant::ShaVariant};
+
+// ---- MGF1 ------------------------------------------------------------------
+
+/// MGF1 (RFC 8017, appendix B.2.1): fill `mask` from `seed`.
+pub fn mgf1<V: ShaVariant, const B: usize, const D: usize>(seed: &[u8], mask: &mut [u8]) {
+    mask.fill(0);
+    mgf1_xor::<V, B, D>(seed, mask);
+}
+
+/// XOR the MGF1 mask of `seed` into `data`, as every masking step does.
+pub fn mgf1_xor<V: ShaVariant, const B: usize, const D: usize>(seed: &[u8], data: &mut [u8]) {
+    let mut prefix = ShaHasher::<V, B, D>::new();
+    prefix.update(seed);
+    for (counter, chunk) in (0..=u32::MAX).zip(data.chunks_mut(D)) {
+        let mask = prefix.clone().finalize_with(&counter.to_be_bytes());
+        for (d, m) in chunk.iter_mut().zip(mask) {
+            *d ^= m;
+        }
+    }
+}
+
+// ---- EMSA-PSS --------------------------------------------------------------
+
+/// `H(0x00 * 8 || mHash || salt)`.
+fn pss_hash<V: ShaVariant, const B: usize, const D: usize>(
+    m_hash: &[u8; D],
+    salt: &[u8],
+) -> [u8; D] {
+    let mut h = ShaHasher::<V, B, D>::new();
+    h.update(&[0u8; 8]);
+    h.update(m_hash);
+    h.finalize_with(salt)
+}
+
+/// Mask keeping the low `em_bits` bits of the leading byte.
+fn top_mask(em_len: usize, em_bits: usize) -> u8 {
+    0xff >> (8 * em_len - em_bits)
+}
+
+/// EMSA-PSS-ENCODE (RFC 8017, section 9.1.1) of the message hash `m_hash`
+/// for a modulus of `em_bits + 1` bits.
+///
+/// Fails if `em` is not `ceil(em_bits / 8)` bytes long or too short for
+/// the digest and `salt`.
+pub fn pss_encode<V: ShaVariant, const B: usize, const D: usize>(
+    m_hash: &[u8; D],
+    salt: &[u8],
+    em_bits: usize,
+    em: &mut [u8],
+) -> Result<(), &'static str> {
+    let em_len = em_bits.div_ceil(8);
+    if em.len() != em_len {
+        return Err("em must be ceil(em_bits / 8) bytes long");
+    }
+    if em_len < D + salt.len() + 2 {
+        return Err("encoding error");
+    }
+
+    let h = pss_hash::<V, B, D>(m_hash, salt);
+    let (db, tail) = em.split_at_mut(em_len - D - 1);
+    let ps = db.len() - salt.len() - 1;
+    db[..ps].fill(0);
+    db[ps] = 0x01;
+    db[ps + 1..].copy_from_slice(salt);
+    mgf1_xor::<V, B, D>(&h, db);
+    db[0] &= top_mask(em_len, em_bits);
+
+    tail[..D].copy_from_slice(&h);
+    tail[D] = 0xbc;
+    Ok(())
+}
+
+/// EMSA-PSS-VERIFY (RFC 8017, section 9.1.2): whether `em` encodes
+/// `m_hash` for a modulus of `em_bits + 1` bits.
+///
+/// With `salt_len` of `None` the salt length is recovered from the
+/// encoding, as when verifying signatures whose salt length is not fixed.
+pub fn pss_verify<V: ShaVariant, const B: usize, const D: usize>(
+    m_hash: &[u8; D],
+    em: &[u8],
+    em_bits: usize,
+    salt_len: Option<usize>,
+) -> bool {
+    let em_len = em_bits.div_ceil(8);
+    let Some(min_len) = salt_len.unwrap_or(0).checked_add(D + 2) else {
+        return false;
+    };
+    if em.len() != em_len || em_len < min_len {
+        return false;
+    }
+    if em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let (masked_db, tail) = em.split_at(em_len - D - 1);
+    let h = &tail[..D];
+    let top = top_mask(em_len, em_bits);
+    if masked_db[0] & !top != 0 {
+        return false;
+    }
+
+    // Unmask DB one MGF1 block at a time, hashing the salt as it appears.
+    let ps_len = salt_len.map(|s| masked_db.len() - s - 1);
+    let mut hasher = ShaHasher::<V, B, D>::new();
+    hasher.update(&[0u8; 8]);
+    hasher.update(m_hash);
+    let mut prefix = ShaHasher::<V, B, D>::new();
+    prefix.update(h);
+    let mut in_salt = false;
+
+    for (counter, chunk) in (0..=u32::MAX).zip(masked_db.chunks(D)) {
+        let mut db = prefix.clone().finalize_with(&counter.to_be_bytes());
+        for (d, m) in db.iter_mut().zip(chunk) {
+            *d ^= m;
+        }
+        if counter == 0 {
+            db[0] &= top;
+        }
+        let db = &db[..chunk.len()];
+        if in_salt {
+            hasher.update(db);
+            continue;
+        }
+
+        let offset = counter as usize * D;
+        for (j, &byte) in db.iter().enumerate() {
+            match byte {
+                0x00 if ps_len.is_none_or(|ps| offset + j < ps) => {}
+                0x01 if ps_len.is_none_or(|ps| offset + j == ps) => {
+                    hasher.update(&db[j + 1..]);
+                    in_salt = true;
+                    break;
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    in_salt && ct_eq(&hasher.finalize(), h)
+}
+
+// ---- EME-OAEP --------------------------------------------------------------
+
+/// EME-OAEP encoding (RFC 8017, section 7.1.1) of `message` under
+/// `label`, masked with `seed`, filling the `k`-byte `em`.
+///
+/// Fails if `em` is shorter than `2 * DIGEST + 2` bytes or `message`
+/// longer than `k - 2 * DIGEST - 2` bytes.
+pub fn oaep_encode<V: ShaVariant, const B: usize, const D: usize>(
+    message: &[u8],
+    label: &[u8],
+    seed: &[u8; D],
+    em: &mut [u8],
+) -> Result<(), &'static str> {
+    let k = em.len();
+    if k < 2 * D + 2 {
+        return Err("modulus too short");
+    }
+    if message.len() > k - 2 * D - 2 {
+        return Err("message too long");
+    }
+
+    let (y, rest) = em.split_at_mut(1);
+    let (masked_seed, db) = rest.split_at_mut(D);
+    y[0] = 0x00;
+    db[..D].copy_from_slice(&ShaHasher::<V, B, D>::new().finalize_with(label));
+    let ps = db.len() - D - message.len() - 1;
+    db[D..D + ps].fill(0);
+    db[D + ps] = 0x01;
+    db[D + ps + 1..].copy_from_slice(message);
+
+    mgf1_xor::<V, B, D>(seed, db);
+    masked_seed.copy_from_slice(seed);
+    mgf1_xor::<V, B, D>(db, masked_seed);
+    Ok(())
+}
+
+/// EME-OAEP decoding (RFC 8017, section 7.1.2) of the `k`-byte `em`
+/// under `label`, unmasking in place. Returns the message within `em`.
+///
+/// All checks run in time that depends only on `k`, and every failure
+/// is the same `"decryption error"`.
+pub fn oaep_decode<'a, V: ShaVariant, const B: usize, const D: usize>(
+    em: &'a mut [u8],
+    label: &[u8],
+) -> Result<&'a [u8], &'static str> {
+    if em.len() < 2 * D + 2 {
+        return Err("decryption error");
+    }
+
+    let (y, rest) = em.split_at_mut(1);
+    let (seed, db) = rest.split_at_mut(D);
+    mgf1_xor::<V, B, D>(db, seed);
+    mgf1_xor::<V, B, D>(seed, db);
+
+    let l_hash = ShaHasher::<V, B, D>::new().finalize_with(label);
+    let mut good = ct_is_zero(y[0]) & 0u8.wrapping_sub(ct_eq(&db[..D], &l_hash) as u8);
+
+    // Find the 0x01 separator without branching on the data.
+    let mut found = 0u8;
+    let mut invalid = 0u8;
+    let mut separator = 0usize;
+    for (i, &byte) in db[D..].iter().enumerate() {
+        let is_one = ct_is_zero(byte ^ 0x01);
+        let is_zero = ct_is_zero(byte);
+        separator |= i & 0usize.wrapping_sub((is_one & !found & 1) as usize);
+        invalid |= !found & !is_zero & !is_one;
+        found |= is_one;
+    }
+    good &= found & !invalid;
+
+    if ::core::hint::black_box(good) != 0xff {
+        return Err("decryption error");
+    }
+    Ok(&em[1 + 2 * D + separator + 1..])
+}
+
+/// `0xff` if `x == 0`, else `0x00`, without branching.
+fn ct_is_zero(x: u8) -> u8 {
+    ((x as u32).wrapping_sub(1) >> 8) as u8
+}
+
+// ---- EMSA-PKCS1-v1_5 -------------------------------------------------------
+
+/// EMSA-PKCS1-v1_5 encoding (RFC 8017, section 9.2) of the message hash
+/// `m_hash`, filling `em`.
+///
+/// Fails if `V` has no [`DIGEST_INFO_PREFIX`](ShaVariant::DIGEST_INFO_PREFIX)
+/// or `em` is too short for the `DigestInfo` and eight bytes of padding.
+pub fn pkcs1v15_encode<V: ShaVariant, const B: usize, const D: usize>(
+    m_hash: &[u8; D],
+    em: &mut [u8],
+) -> Result<(), &'static str> {
+    let prefix = V::DIGEST_INFO_PREFIX;
+    if prefix.is_empty() {
+        return Err("hash has no DigestInfo encoding");
+    }
+    let t_len = prefix.len() + D;
+    if em.len() < t_len + 11 {
+        return Err("intended encoded message length too short");
+    }
+
+    let ps_end = em.len() - t_len - 1;
+    em[0] = 0x00;
+    em[1] = 0x01;
+    em[2..ps_end].fill(0xff);
+    em[ps_end] = 0x00;
+    em[ps_end + 1..ps_end + 1 + prefix.len()].copy_from_slice(prefix);
+    em[ps_end + 1 + prefix.len()..].copy_from_slice(m_hash);
+    Ok(())
+}
+
+/// Whether `em` is the EMSA-PKCS1-v1_5 encoding of `m_hash`, compared in
+/// time that depends only on the lengths.
+pub fn pkcs1v15_verify<V: ShaVariant, const B: usize, const D: usize>(
+    m_hash: &[u8; D],
+    em: &[u8],
+) -> bool {
+    let prefix = V::DIGEST_INFO_PREFIX;
+    let t_len = prefix.len() + D;
+    if prefix.is_empty() || em.len() < t_len + 11 {
+        return false;
+    }
+
+    let ps_end = em.len() - t_len - 1;
+    let mut diff = em[0] | (em[1] ^ 0x01) | em[ps_end];
+    diff |= em[2..ps_end].iter().fold(0, |acc, b| acc | (b ^ 0xff));
+    (::core::hint::black_box(diff) == 0)
+        & ct_eq(&em[ps_end + 1..em.len() - D], prefix)
+        & ct_eq(&em[em.len() - D..], m_hash)
+}
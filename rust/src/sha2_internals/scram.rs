@@ -0,0 +1,553 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! SCRAM authentication (RFC 5802), as `SCRAM-SHA-256` (RFC 7677) and
+//! `SCRAM-SHA-512` with the matching variant.
+//!
+//! [`ScramClient`] and [`ScramServer`] produce and consume the four
+//! messages of an exchange; the transport and the SASL framing are the
+//! caller's. Nonces are supplied by the caller and must come from a
+//! cryptographically secure source. Usernames and passwords are used as
+//! given, so callers that need SASLprep apply it first. Channel binding
+//! is not supported: the client sends the `n` flag and the server
+//! rejects `p`.
+//!
+//! ```
+//! # #[cfg(feature = "sha256")] {
+//! use sha2::scram::{Credentials, ScramClient, ScramServer};
+//! use sha2::Sha256Cfg;
+//!
+//! let stored = Credentials::<32>::new::<Sha256Cfg, 64>(b"pencil", b"salt", 4096);
+//!
+//! let mut client = ScramClient::<Sha256Cfg, 64, 32>::new("user", "clientnonce").unwrap();
+//! let mut server = ScramServer::<Sha256Cfg, 64, 32>::new(client.client_first()).unwrap();
+//! assert_eq!(server.username(), "user");
+//! let server_first = server.server_first("servernonce", &stored).unwrap();
+//! let client_final = client.client_final(&server_first, b"pencil").unwrap();
+//! let server_final = server.server_final(&client_final).unwrap();
+//! client.verify_server_final(&server_final).unwrap();
+//! # }
+//! ```
+
+use ::std::{string::String, vec::Vec};
+
+use super::{hasher::ShaHasher, hmac::Hmac, hmac::ct_eq, variant::ShaVariant};
+
+/// Fewest iterations a client accepts, as RFC 7677 recommends.
+pub const MIN_ITERATIONS: u32 = 4096;
+
+/// Most iterations a client accepts by default, so a hostile server
+/// cannot make it spin for minutes. See
+/// [`ScramClient::set_max_iterations`].
+pub const MAX_ITERATIONS: u32 = 1_000_000;
+
+/// `Hi(password, salt, i)`: PBKDF2 with a single block of output.
+fn hi<V: ShaVariant, const B: usize, const D: usize>(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+) -> [u8; D] {
+    let mut mac = Hmac::<V, B, D>::new(password);
+    mac.update(salt);
+    mac.update(&1u32.to_be_bytes());
+    let mut u = mac.finalize();
+    let mut out = u;
+    for _ in 1..iterations {
+        mac.update(&u);
+        u = mac.finalize();
+        for (o, x) in out.iter_mut().zip(u) {
+            *o ^= x;
+        }
+    }
+    out
+}
+
+/// What a server stores for each user, in place of the password.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Credentials<const DIGEST: usize> {
+    /// The per-user salt, stored and sent base64-encoded as the `s`
+    /// attribute of the server-first-message.
+    pub salt: Vec<u8>,
+    /// The PBKDF2 iteration count, stored and sent as the `i` attribute
+    /// of the server-first-message.
+    pub iterations: u32,
+    /// `H(ClientKey)`.
+    pub stored_key: [u8; DIGEST],
+    /// `HMAC(SaltedPassword, "Server Key")`.
+    pub server_key: [u8; DIGEST],
+}
+
+impl<const D: usize> Credentials<D> {
+    /// Derive the credentials for `password`, with `D` the digest size
+    /// of `V`.
+    ///
+    /// # Panics
+    ///
+    /// If `iterations` is zero.
+    pub fn new<V: ShaVariant, const B: usize>(
+        password: &[u8],
+        salt: &[u8],
+        iterations: u32,
+    ) -> Self {
+        assert!(iterations > 0, "iterations must be non-zero");
+        let keys = Keys::<V, B, D>::new(&hi::<V, B, D>(password, salt, iterations));
+        Self {
+            salt: salt.to_vec(),
+            iterations,
+            stored_key: keys.stored_key,
+            server_key: keys.server_key,
+        }
+    }
+}
+
+/// The keys derived from `SaltedPassword`.
+struct Keys<V: ShaVariant, const B: usize, const D: usize> {
+    client_key: [u8; D],
+    stored_key: [u8; D],
+    server_key: [u8; D],
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Keys<V, B, D> {
+    fn new(salted_password: &[u8; D]) -> Self {
+        let client_key = Hmac::<V, B, D>::mac(salted_password, b"Client Key");
+        Self {
+            client_key,
+            stored_key: ShaHasher::<V, B, D>::new().finalize_with(&client_key),
+            server_key: Hmac::<V, B, D>::mac(salted_password, b"Server Key"),
+            _variant: ::core::marker::PhantomData,
+        }
+    }
+}
+
+/// `client-first-message-bare "," server-first-message ","
+/// client-final-message-without-proof`.
+fn auth_message(client_first_bare: &str, server_first: &str, client_final_bare: &str) -> String {
+    [client_first_bare, server_first, client_final_bare].join(",")
+}
+
+// ---- Client ----------------------------------------------------------------
+
+enum ClientState<const D: usize> {
+    Initial,
+    /// Waiting for `server-final-message`.
+    Final {
+        server_signature: [u8; D],
+    },
+    Done,
+    /// A step failed; the exchange is over.
+    Failed,
+}
+
+/// The client side of a SCRAM exchange.
+///
+/// Each step is only valid after the previous one; calling one out of
+/// order, or again after a step has failed, fails with
+/// `"wrong SCRAM stage"`.
+pub struct ScramClient<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    /// `gs2-header || client-first-message-bare`.
+    client_first: String,
+    max_iterations: u32,
+    state: ClientState<DIGEST>,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> ScramClient<V, B, D> {
+    /// Start an exchange for `username` with the client nonce `nonce`.
+    ///
+    /// Fails if `nonce` is empty or has characters other than printable
+    /// ASCII without `,`.
+    pub fn new(username: &str, nonce: &str) -> Result<Self, &'static str> {
+        check_nonce(nonce)?;
+        let mut client_first = String::from("n,,n=");
+        escape_saslname(username, &mut client_first);
+        client_first.push_str(",r=");
+        client_first.push_str(nonce);
+        Ok(Self {
+            client_first,
+            max_iterations: MAX_ITERATIONS,
+            state: ClientState::Initial,
+            _variant: ::core::marker::PhantomData,
+        })
+    }
+
+    /// `client-first-message`, to send first.
+    pub fn client_first(&self) -> &str {
+        &self.client_first
+    }
+
+    /// Reject servers that ask for more than `max` iterations, at least
+    /// [`MIN_ITERATIONS`]. The default is [`MAX_ITERATIONS`].
+    pub fn set_max_iterations(&mut self, max: u32) {
+        self.max_iterations = max.max(MIN_ITERATIONS);
+    }
+
+    fn client_first_bare(&self) -> &str {
+        &self.client_first[GS2_HEADER.len()..]
+    }
+
+    /// Answer `server-first-message` with `client-final-message`.
+    ///
+    /// Fails if the server's nonce does not extend the client's, the salt
+    /// is not base 64, the iteration count is below [`MIN_ITERATIONS`] or
+    /// above the maximum, or the server asks for an unsupported extension.
+    /// The exchange cannot continue after a failure.
+    pub fn client_final(
+        &mut self,
+        server_first: &str,
+        password: &[u8],
+    ) -> Result<String, &'static str> {
+        if !matches!(self.state, ClientState::Initial) {
+            return Err("wrong SCRAM stage");
+        }
+        self.state = ClientState::Failed;
+        let mut attrs = Attributes::new(server_first);
+        if server_first.starts_with("m=") {
+            return Err("unsupported mandatory extension");
+        }
+        let nonce = attrs.expect('r')?;
+        let salt = attrs.expect('s')?;
+        let iterations = attrs.expect('i')?;
+
+        let client_nonce = self.client_first.rsplit_once(",r=").map_or("", |(_, n)| n);
+        if !nonce.starts_with(client_nonce) || nonce.len() == client_nonce.len() {
+            return Err("server nonce does not extend the client nonce");
+        }
+        check_nonce(nonce)?;
+        let salt = base64_decode(salt)?;
+        let iterations = parse_iterations(iterations)?;
+        if iterations < MIN_ITERATIONS {
+            return Err("iteration count too low");
+        }
+        if iterations > self.max_iterations {
+            return Err("iteration count too high");
+        }
+
+        let mut client_final = String::from("c=");
+        base64_encode(GS2_HEADER.as_bytes(), &mut client_final);
+        client_final.push_str(",r=");
+        client_final.push_str(nonce);
+
+        let keys = Keys::<V, B, D>::new(&hi::<V, B, D>(password, &salt, iterations));
+        let auth_message = auth_message(self.client_first_bare(), server_first, &client_final);
+        let client_signature = Hmac::<V, B, D>::mac(&keys.stored_key, auth_message.as_bytes());
+        let proof: [u8; D] = ::core::array::from_fn(|i| keys.client_key[i] ^ client_signature[i]);
+        client_final.push_str(",p=");
+        base64_encode(&proof, &mut client_final);
+
+        self.state = ClientState::Final {
+            server_signature: Hmac::<V, B, D>::mac(&keys.server_key, auth_message.as_bytes()),
+        };
+        Ok(client_final)
+    }
+
+    /// Check `server-final-message`, which proves the server knew the
+    /// credentials. The exchange has succeeded only once this returns
+    /// `Ok`; an `Err` ends it.
+    pub fn verify_server_final(&mut self, server_final: &str) -> Result<(), &'static str> {
+        let ClientState::Final { server_signature } = self.state else {
+            return Err("wrong SCRAM stage");
+        };
+        self.state = ClientState::Failed;
+        if server_final.starts_with("e=") {
+            return Err("server reported an error");
+        }
+        let verifier = Attributes::new(server_final).expect('v')?;
+        let verifier = base64_decode(verifier)?;
+        if !ct_eq(&verifier, &server_signature) {
+            return Err("invalid server signature");
+        }
+        self.state = ClientState::Done;
+        Ok(())
+    }
+}
+
+// ---- Server ----------------------------------------------------------------
+
+enum ServerState<const D: usize> {
+    Initial,
+    /// Waiting for `client-final-message`.
+    Final {
+        server_first: String,
+        stored_key: [u8; D],
+        server_key: [u8; D],
+    },
+    Done,
+    /// A step failed; the exchange is over.
+    Failed,
+}
+
+/// The server side of a SCRAM exchange.
+///
+/// Each step is only valid after the previous one; calling one out of
+/// order, or again after a step has failed, fails with
+/// `"wrong SCRAM stage"`.
+pub struct ScramServer<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    gs2_header: String,
+    client_first_bare: String,
+    username: String,
+    client_nonce: String,
+    state: ServerState<DIGEST>,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> ScramServer<V, B, D> {
+    /// Start an exchange from the received `client-first-message`.
+    ///
+    /// Fails on malformed messages, channel binding requests and
+    /// mandatory extensions.
+    pub fn new(client_first: &str) -> Result<Self, &'static str> {
+        let (flag, rest) = client_first.split_once(',').ok_or("malformed message")?;
+        match flag {
+            "n" | "y" => {}
+            _ if flag.starts_with("p=") => return Err("channel binding not supported"),
+            _ => return Err("malformed message"),
+        }
+        let (authzid, bare) = rest.split_once(',').ok_or("malformed message")?;
+        if !authzid.is_empty() && !authzid.starts_with("a=") {
+            return Err("malformed message");
+        }
+        if bare.starts_with("m=") {
+            return Err("unsupported mandatory extension");
+        }
+
+        let mut attrs = Attributes::new(bare);
+        let username = unescape_saslname(attrs.expect('n')?)?;
+        let nonce = attrs.expect('r')?;
+        check_nonce(nonce)?;
+        Ok(Self {
+            gs2_header: client_first[..client_first.len() - bare.len()].into(),
+            client_first_bare: bare.into(),
+            username,
+            client_nonce: nonce.into(),
+            state: ServerState::Initial,
+            _variant: ::core::marker::PhantomData,
+        })
+    }
+
+    /// The unescaped username, to look up the user's [`Credentials`].
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// `server-first-message`, appending `nonce` to the client's nonce.
+    ///
+    /// For unknown users, pass made-up credentials that are stable per
+    /// username and let the exchange fail at [`server_final`](Self::server_final),
+    /// so the reply does not reveal which users exist.
+    pub fn server_first(
+        &mut self,
+        nonce: &str,
+        credentials: &Credentials<D>,
+    ) -> Result<String, &'static str> {
+        if !matches!(self.state, ServerState::Initial) {
+            return Err("wrong SCRAM stage");
+        }
+        check_nonce(nonce)?;
+        let mut server_first = String::from("r=");
+        server_first.push_str(&self.client_nonce);
+        server_first.push_str(nonce);
+        server_first.push_str(",s=");
+        base64_encode(&credentials.salt, &mut server_first);
+        server_first.push_str(",i=");
+        server_first.push_str(&credentials.iterations.to_string());
+
+        self.state = ServerState::Final {
+            server_first: server_first.clone(),
+            stored_key: credentials.stored_key,
+            server_key: credentials.server_key,
+        };
+        Ok(server_first)
+    }
+
+    /// Check `client-final-message` and answer with
+    /// `server-final-message`.
+    ///
+    /// On `Err` authentication has failed and the exchange is over; the
+    /// caller may send `e=invalid-proof` or simply close it.
+    pub fn server_final(&mut self, client_final: &str) -> Result<String, &'static str> {
+        let state = ::core::mem::replace(&mut self.state, ServerState::Failed);
+        let ServerState::Final {
+            server_first,
+            stored_key,
+            server_key,
+        } = state
+        else {
+            self.state = state;
+            return Err("wrong SCRAM stage");
+        };
+        let (client_final_bare, proof) =
+            client_final.rsplit_once(",p=").ok_or("malformed message")?;
+        let mut attrs = Attributes::new(client_final_bare);
+        let binding = base64_decode(attrs.expect('c')?)?;
+        let nonce = attrs.expect('r')?;
+        if binding != self.gs2_header.as_bytes() {
+            return Err("channel binding mismatch");
+        }
+        if Some(nonce)
+            != server_first
+                .strip_prefix("r=")
+                .and_then(|s| s.split(',').next())
+        {
+            return Err("nonce mismatch");
+        }
+        let proof = base64_decode(proof)?;
+        if proof.len() != D {
+            return Err("invalid proof");
+        }
+
+        let auth_message = auth_message(&self.client_first_bare, &server_first, client_final_bare);
+        let client_signature = Hmac::<V, B, D>::mac(&stored_key, auth_message.as_bytes());
+        let client_key: [u8; D] = ::core::array::from_fn(|i| proof[i] ^ client_signature[i]);
+        let computed = ShaHasher::<V, B, D>::new().finalize_with(&client_key);
+        if !ct_eq(&computed, &stored_key) {
+            return Err("invalid proof");
+        }
+
+        let mut server_final = String::from("v=");
+        base64_encode(
+            &Hmac::<V, B, D>::mac(&server_key, auth_message.as_bytes()),
+            &mut server_final,
+        );
+        self.state = ServerState::Done;
+        Ok(server_final)
+    }
+}
+
+// ---- Message syntax --------------------------------------------------------
+
+/// The header the client sends: no channel binding, no authzid.
+const GS2_HEADER: &str = "n,,";
+
+/// Walks the `k=value` attributes of a message in order.
+struct Attributes<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Attributes<'a> {
+    fn new(message: &'a str) -> Self {
+        Self {
+            rest: Some(message),
+        }
+    }
+
+    /// The value of the next attribute, which must be `key`.
+    fn expect(&mut self, key: char) -> Result<&'a str, &'static str> {
+        let rest = self.rest.ok_or("malformed message")?;
+        let (attr, rest) = match rest.split_once(',') {
+            Some((attr, rest)) => (attr, Some(rest)),
+            None => (rest, None),
+        };
+        self.rest = rest;
+        attr.strip_prefix(key)
+            .and_then(|a| a.strip_prefix('='))
+            .ok_or("malformed message")
+    }
+}
+
+/// Nonces are printable ASCII other than `,`.
+fn check_nonce(nonce: &str) -> Result<(), &'static str> {
+    if nonce.is_empty()
+        || !nonce
+            .bytes()
+            .all(|b| (0x21..=0x7e).contains(&b) && b != b',')
+    {
+        return Err("invalid nonce");
+    }
+    Ok(())
+}
+
+fn parse_iterations(s: &str) -> Result<u32, &'static str> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("invalid iteration count");
+    }
+    s.parse().map_err(|_| "invalid iteration count")
+}
+
+fn escape_saslname(name: &str, out: &mut String) {
+    for c in name.chars() {
+        match c {
+            ',' => out.push_str("=2C"),
+            '=' => out.push_str("=3D"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn unescape_saslname(name: &str) -> Result<String, &'static str> {
+    let mut out = String::with_capacity(name.len());
+    let mut parts = name.split('=');
+    out.push_str(parts.next().unwrap_or_default());
+    for part in parts {
+        if let Some(rest) = part.strip_prefix("2C") {
+            out.push(',');
+            out.push_str(rest);
+        } else if let Some(rest) = part.strip_prefix("3D") {
+            out.push('=');
+            out.push_str(rest);
+        } else {
+            return Err("invalid username");
+        }
+    }
+    Ok(out)
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Padded RFC 4648 base 64.
+fn base64_encode(data: &[u8], out: &mut String) {
+    for chunk in data.chunks(3) {
+        let w = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |w, (i, &b)| w | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(w >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
+
+fn base64_decode(s: &str) -> Result<Vec<u8>, &'static str> {
+    const ERR: &str = "invalid base64";
+    let s = s.as_bytes();
+    if !s.len().is_multiple_of(4) {
+        return Err(ERR);
+    }
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    for (n, quad) in s.chunks(4).enumerate() {
+        let last = n == s.len() / 4 - 1;
+        let pad = quad.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 || (pad > 0 && !last) {
+            return Err(ERR);
+        }
+        let mut w = 0u32;
+        for &c in &quad[..4 - pad] {
+            let v = BASE64.iter().position(|&a| a == c).ok_or(ERR)?;
+            w = (w << 6) | v as u32;
+        }
+        w <<= 6 * pad as u32;
+        let bytes = w.to_be_bytes();
+        // Bits dropped by padding must be zero.
+        if bytes[4 - pad..].iter().any(|&b| b != 0) {
+            return Err(ERR);
+        }
+        out.extend_from_slice(&bytes[1..4 - pad]);
+    }
+    Ok(out)
+}
@@ -0,0 +1,108 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+//! SHA-256 compression with the x86 SHA extensions (SHA-NI).
+//!
+//! State is kept in the `ABEF`/`CDGH` register layout the `sha256rnds2`
+//! instruction expects; each step schedules four message words with
+//! `sha256msg1`/`sha256msg2` and runs four rounds.
+//!
+//! Shared by SHA-224 and SHA-256.
+
+#[cfg(target_arch = "x86")]
+use ::core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use ::core::arch::x86_64::*;
+
+use super::family::K32;
+
+/// Whether the running CPU supports every instruction used here.
+pub(super) fn detect() -> bool {
+    #[cfg(any(feature = "std", test))]
+    {
+        ::std::is_x86_feature_detected!("sha")
+            && ::std::is_x86_feature_detected!("sse4.1")
+            && ::std::is_x86_feature_detected!("ssse3")
+    }
+    #[cfg(not(any(feature = "std", test)))]
+    {
+        cfg!(all(
+            target_feature = "sha",
+            target_feature = "sse4.1",
+            target_feature = "ssse3"
+        ))
+    }
+}
+
+/// `W[t..t+4]` from the four preceding groups of four words.
+#[inline]
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+fn schedule(w16: __m128i, w12: __m128i, w8: __m128i, w4: __m128i) -> __m128i {
+    // w[t-16] + σ0(w[t-15]), then + w[t-7], then + σ1(w[t-2]).
+    let t = _mm_sha256msg1_epu32(w16, w12);
+    let t = _mm_add_epi32(t, _mm_alignr_epi8::<4>(w4, w8));
+    _mm_sha256msg2_epu32(t, w4)
+}
+
+/// Compress one block. Only call once [`detect`] has returned `true`.
+#[target_feature(enable = "sha,sse2,ssse3,sse4.1")]
+pub(super) fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    // Byte swap within each 32-bit lane.
+    let bswap = _mm_set_epi64x(0x0c0d_0e0f_0809_0a0b, 0x0405_0607_0001_0203);
+
+    // SAFETY: `state` is 32 readable bytes; `loadu` has no alignment requirement.
+    let (dcba, hgfe) = unsafe {
+        let p = state.as_ptr().cast::<__m128i>();
+        (_mm_loadu_si128(p), _mm_loadu_si128(p.add(1)))
+    };
+    let cdab = _mm_shuffle_epi32::<0xb1>(dcba);
+    let efgh = _mm_shuffle_epi32::<0x1b>(hgfe);
+    let mut abef = _mm_alignr_epi8::<8>(cdab, efgh);
+    let mut cdgh = _mm_blend_epi16::<0xf0>(efgh, cdab);
+    let (abef_in, cdgh_in) = (abef, cdgh);
+
+    let mut w: [__m128i; 4] = ::core::array::from_fn(|i| {
+        let src: &[u8; 16] = block[i * 16..].first_chunk().unwrap();
+        // SAFETY: `src` is 16 readable bytes; `loadu` has no alignment requirement.
+        _mm_shuffle_epi8(unsafe { _mm_loadu_si128(src.as_ptr().cast()) }, bswap)
+    });
+
+    for i in 0..16 {
+        if i >= 4 {
+            w[i % 4] = schedule(w[i % 4], w[(i + 1) % 4], w[(i + 2) % 4], w[(i + 3) % 4]);
+        }
+
+        let k: &[u32; 4] = K32[i * 4..].first_chunk().unwrap();
+        // SAFETY: `k` is 16 readable bytes; `loadu` has no alignment requirement.
+        let k = unsafe { _mm_loadu_si128(k.as_ptr().cast()) };
+        let wk = _mm_add_epi32(w[i % 4], k);
+        cdgh = _mm_sha256rnds2_epu32(cdgh, abef, wk);
+        abef = _mm_sha256rnds2_epu32(abef, cdgh, _mm_shuffle_epi32::<0x0e>(wk));
+    }
+
+    abef = _mm_add_epi32(abef, abef_in);
+    cdgh = _mm_add_epi32(cdgh, cdgh_in);
+
+    let feba = _mm_shuffle_epi32::<0x1b>(abef);
+    let dchg = _mm_shuffle_epi32::<0xb1>(cdgh);
+    let dcba = _mm_blend_epi16::<0xf0>(feba, dchg);
+    let hgfe = _mm_alignr_epi8::<8>(dchg, feba);
+
+    // SAFETY: `state` is 32 writable bytes; `storeu` has no alignment requirement.
+    unsafe {
+        let p = state.as_mut_ptr().cast::<__m128i>();
+        _mm_storeu_si128(p, dcba);
+        _mm_storeu_si128(p.add(1), hgfe);
+    }
+}
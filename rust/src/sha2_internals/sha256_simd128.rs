@@ -0,0 +1,126 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+//! SHA-256 compression with a WebAssembly SIMD128 message schedule.
+//!
+//! The same four-words-per-step expansion as the AVX2 SHA-512 schedule:
+//! `w[t-16]`, `σ0(w[t-15])` and `w[t-7]` are computed across all four
+//! lanes, `σ1` is applied to `(w[t-2], w[t-1])` for the low pair and then to
+//! the new low pair for the high pair. `K` is added during expansion.
+//!
+//! WebAssembly has no runtime feature detection, so this module is only
+//! compiled when the `simd128` feature is on and the target enables it.
+//!
+//! Shared by SHA-224 and SHA-256.
+
+use ::core::arch::wasm32::*;
+
+use super::family::{K32, Sha2_32, ShaFamily};
+
+#[inline(always)]
+fn rotr<const R: u32>(x: v128) -> v128 {
+    v128_or(u32x4_shr(x, R), i32x4_shl(x, 32 - R))
+}
+
+#[inline(always)]
+fn small_sigma0(x: v128) -> v128 {
+    v128_xor(v128_xor(rotr::<7>(x), rotr::<18>(x)), u32x4_shr(x, 3))
+}
+
+#[inline(always)]
+fn small_sigma1(x: v128) -> v128 {
+    v128_xor(v128_xor(rotr::<17>(x), rotr::<19>(x)), u32x4_shr(x, 10))
+}
+
+#[inline(always)]
+fn load(w: &[u32], at: usize) -> v128 {
+    u32x4(w[at], w[at + 1], w[at + 2], w[at + 3])
+}
+
+#[inline(always)]
+fn store(dst: &mut [u32], v: v128) {
+    dst[0] = u32x4_extract_lane::<0>(v);
+    dst[1] = u32x4_extract_lane::<1>(v);
+    dst[2] = u32x4_extract_lane::<2>(v);
+    dst[3] = u32x4_extract_lane::<3>(v);
+}
+
+/// Expand `w[16..64]` in place and return `w[t] + K[t]` for all 64 rounds.
+#[inline(always)]
+fn schedule(w: &mut [u32; 64]) -> [u32; 64] {
+    let mut wk = [0u32; 64];
+
+    let mut t = 16;
+    while t < 64 {
+        // Lane-parallel part: w[t-16] + σ0(w[t-15]) + w[t-7].
+        let partial = i32x4_add(
+            i32x4_add(load(w, t - 16), small_sigma0(load(w, t - 15))),
+            load(w, t - 7),
+        );
+
+        // σ1 over (w[t-2], w[t-1]) completes the low pair ...
+        let last = load(w, t - 4);
+        let prev = i32x4_shuffle::<2, 3, 2, 3>(last, last);
+        let low = i32x4_add(partial, small_sigma1(prev));
+        // ... and σ1 over the new low pair completes the high pair.
+        let carried = i32x4_shuffle::<0, 1, 0, 1>(low, low);
+        let next = i32x4_add(partial, small_sigma1(carried));
+        let v = i32x4_shuffle::<0, 1, 6, 7>(low, next);
+
+        store(&mut w[t..], v);
+        t += 4;
+    }
+
+    t = 0;
+    while t < 64 {
+        store(&mut wk[t..], i32x4_add(load(w, t), load(&K32, t)));
+        t += 4;
+    }
+
+    wk
+}
+
+#[inline(always)]
+pub(super) fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut w = [0u32; 64];
+    for (t, wt) in w.iter_mut().take(16).enumerate() {
+        *wt = Sha2_32::block_word(block, t);
+    }
+    let wk = schedule(&mut w);
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for &wk in wk.iter() {
+        let ch = g ^ (e & (f ^ g));
+        let maj = (a & b) ^ (c & (a ^ b));
+        let t1 = h
+            .wrapping_add(Sha2_32::big_sigma1(e))
+            .wrapping_add(ch)
+            .wrapping_add(wk);
+        let t2 = Sha2_32::big_sigma0(a).wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
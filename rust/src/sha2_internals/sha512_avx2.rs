@@ -0,0 +1,177 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! SHA-512 compression with the message schedule vectorised alongside the
+//! rounds.
+//!
+//! The schedule lives in a ring of eight 128-bit registers, two words
+//! each. Every pair of scalar rounds reads `w[t] + K[t]` from a 16-word
+//! buffer and then refills the same slots with the pair 16 rounds ahead,
+//! so the vector expansion overlaps the scalar rounds instead of running
+//! ahead of them. Pairs suit `σ1`: `w[t-2]` and `w[t-1]` are exactly the
+//! previous pair. The rounds are fully unrolled and use BMI2 `rorx` for
+//! the `Σ` rotations.
+//!
+//! Shared by SHA-384, SHA-512, SHA-512/224 and SHA-512/256.
+//!
+//! The x86 SHA512 instructions (`VSHA512RNDS2`, `VSHA512MSG1`,
+//! `VSHA512MSG2`) have no backend yet.
+
+#[cfg(target_arch = "x86")]
+use ::core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use ::core::arch::x86_64::*;
+
+use super::family::{K64, Sha2_64, ShaFamily};
+
+/// Whether the running CPU supports AVX2 and BMI2.
+pub(super) fn detect() -> bool {
+    #[cfg(any(feature = "std", test))]
+    {
+        ::std::is_x86_feature_detected!("avx2") && ::std::is_x86_feature_detected!("bmi2")
+    }
+    #[cfg(not(any(feature = "std", test)))]
+    {
+        cfg!(all(target_feature = "avx2", target_feature = "bmi2"))
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn rotr<const R: i32, const L: i32>(x: __m128i) -> __m128i {
+    _mm_or_si128(_mm_srli_epi64::<R>(x), _mm_slli_epi64::<L>(x))
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn small_sigma0(x: __m128i) -> __m128i {
+    _mm_xor_si128(
+        _mm_xor_si128(rotr::<1, 63>(x), rotr::<8, 56>(x)),
+        _mm_srli_epi64::<7>(x),
+    )
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn small_sigma1(x: __m128i) -> __m128i {
+    _mm_xor_si128(
+        _mm_xor_si128(rotr::<19, 45>(x), rotr::<61, 3>(x)),
+        _mm_srli_epi64::<6>(x),
+    )
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn load(src: &[u8], at: usize) -> __m128i {
+    let src: &[u8; 16] = src[at..].first_chunk().unwrap();
+    // SAFETY: `src` is 16 readable bytes; `loadu` has no alignment requirement.
+    unsafe { _mm_loadu_si128(src.as_ptr().cast()) }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn load_k(t: usize) -> __m128i {
+    let k: &[u64; 2] = K64[t..].first_chunk().unwrap();
+    // SAFETY: `k` is 16 readable bytes; `loadu` has no alignment requirement.
+    unsafe { _mm_loadu_si128(k.as_ptr().cast()) }
+}
+
+#[inline]
+#[target_feature(enable = "avx2")]
+fn store(dst: &mut [u64; 16], at: usize, v: __m128i) {
+    let dst: &mut [u64; 2] = dst[at..].first_chunk_mut().unwrap();
+    // SAFETY: `dst` is 16 writable bytes; `storeu` has no alignment requirement.
+    unsafe { _mm_storeu_si128(dst.as_mut_ptr().cast(), v) }
+}
+
+/// `w[t..t+2]` from the ring `x`, where `x[j]` holds `w[t-16..t-14]`.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn expand(x: &[__m128i; 8], j: usize) -> __m128i {
+    let w15 = _mm_alignr_epi8::<8>(x[(j + 1) % 8], x[j]);
+    let w7 = _mm_alignr_epi8::<8>(x[(j + 5) % 8], x[(j + 4) % 8]);
+    _mm_add_epi64(
+        _mm_add_epi64(x[j], small_sigma0(w15)),
+        _mm_add_epi64(w7, small_sigma1(x[(j + 7) % 8])),
+    )
+}
+
+/// Compress one block. Only call once [`detect`] has returned `true`.
+#[target_feature(enable = "avx2,bmi2")]
+pub(super) fn compress(state: &mut [u64; 8], block: &[u8; 128]) {
+    // Byte-swap each 64-bit word to big-endian order.
+    let bswap = _mm_set_epi8(8, 9, 10, 11, 12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7);
+    let mut x = [_mm_setzero_si128(); 8];
+    let mut wk = [0u64; 16];
+    for (j, xj) in x.iter_mut().enumerate() {
+        *xj = _mm_shuffle_epi8(load(block, 16 * j), bswap);
+        store(&mut wk, 2 * j, _mm_add_epi64(*xj, load_k(2 * j)));
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    macro_rules! round {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $wk:expr) => {{
+            let t1 = $h
+                .wrapping_add(Sha2_64::big_sigma1($e))
+                .wrapping_add($g ^ ($e & ($f ^ $g)))
+                .wrapping_add($wk);
+            $d = $d.wrapping_add(t1);
+            $h = t1
+                .wrapping_add(Sha2_64::big_sigma0($a))
+                .wrapping_add(($a & $b) ^ ($c & ($a ^ $b)));
+        }};
+    }
+
+    // Two rounds from `wk[2j..]`, then, unless in the last 16 rounds, the
+    // schedule pair 16 rounds ahead into the same slots.
+    macro_rules! step {
+        ($base:expr, $j:expr, $a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident) => {{
+            round!($a, $b, $c, $d, $e, $f, $g, $h, wk[2 * $j]);
+            round!($h, $a, $b, $c, $d, $e, $f, $g, wk[2 * $j + 1]);
+            if $base < 64 {
+                x[$j] = expand(&x, $j);
+                store(
+                    &mut wk,
+                    2 * $j,
+                    _mm_add_epi64(x[$j], load_k($base + 16 + 2 * $j)),
+                );
+            }
+        }};
+    }
+
+    macro_rules! group {
+        ($base:expr) => {{
+            step!($base, 0, a, b, c, d, e, f, g, h);
+            step!($base, 1, g, h, a, b, c, d, e, f);
+            step!($base, 2, e, f, g, h, a, b, c, d);
+            step!($base, 3, c, d, e, f, g, h, a, b);
+            step!($base, 4, a, b, c, d, e, f, g, h);
+            step!($base, 5, g, h, a, b, c, d, e, f);
+            step!($base, 6, e, f, g, h, a, b, c, d);
+            step!($base, 7, c, d, e, f, g, h, a, b);
+        }};
+    }
+
+    group!(0);
+    group!(16);
+    group!(32);
+    group!(48);
+    group!(64);
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
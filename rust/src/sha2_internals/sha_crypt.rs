@@ -0,0 +1,400 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! Ulrich Drepper's SHA-crypt password hashing: `$5$` (SHA-256) and `$6$`
+//! (SHA-512) modular crypt strings, as found in `/etc/shadow`.
+//!
+//! A string reads `$<id>$[rounds=<n>$]<salt>$<checksum>`. Salts longer
+//! than 16 bytes are truncated and round counts are clamped to
+//! `[ROUNDS_MIN, ROUNDS_MAX]`, as the reference implementation does.
+//!
+//! ```
+//! # #[cfg(feature = "sha512")] {
+//! use sha2::sha_crypt::{self, Scheme};
+//!
+//! let stored = sha_crypt::hash_password(Scheme::Sha512, b"hunter2", "saltsalt", None).unwrap();
+//! assert!(sha_crypt::verify(b"hunter2", stored.as_str()));
+//! assert!(!sha_crypt::verify(b"hunter3", stored.as_str()));
+//! # }
+//! ```
+
+use super::{hasher::ShaHasher, hmac::ct_eq, variant::ShaVariant};
+
+#[cfg(feature = "sha256")]
+use super::variant::Sha256Cfg;
+#[cfg(feature = "sha512")]
+use super::variant::Sha512Cfg;
+
+/// Rounds used when the string does not say.
+pub const ROUNDS_DEFAULT: u32 = 5000;
+/// Fewest rounds; smaller requests are raised to this.
+pub const ROUNDS_MIN: u32 = 1000;
+/// Most rounds; larger requests are lowered to this.
+pub const ROUNDS_MAX: u32 = 999_999_999;
+/// Longest salt in bytes; longer salts are truncated.
+pub const SALT_MAX: usize = 16;
+/// Longest crypt string: `$6$rounds=999999999$`, 16 salt bytes, `$` and
+/// 86 checksum characters.
+pub const MAX_LEN: usize = 20 + SALT_MAX + 1 + 86;
+
+const ALPHABET: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A SHA-crypt scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scheme {
+    /// `$5$`, sha256crypt.
+    #[cfg(feature = "sha256")]
+    Sha256,
+    /// `$6$`, sha512crypt.
+    #[cfg(feature = "sha512")]
+    Sha512,
+}
+
+impl Scheme {
+    /// The identifier between the leading dollar signs.
+    pub fn id(self) -> &'static str {
+        match self {
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => "5",
+            #[cfg(feature = "sha512")]
+            Self::Sha512 => "6",
+        }
+    }
+
+    /// Length of the encoded checksum.
+    pub fn checksum_len(self) -> usize {
+        match self {
+            #[cfg(feature = "sha256")]
+            Self::Sha256 => 43,
+            #[cfg(feature = "sha512")]
+            Self::Sha512 => 86,
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        match id {
+            #[cfg(feature = "sha256")]
+            "5" => Some(Self::Sha256),
+            #[cfg(feature = "sha512")]
+            "6" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of a crypt string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parsed<'a> {
+    /// `$5$` or `$6$`.
+    pub scheme: Scheme,
+    /// The clamped `rounds=` value, if the string has one.
+    pub rounds: Option<u32>,
+    /// The salt, truncated to [`SALT_MAX`] bytes.
+    pub salt: &'a str,
+    /// The checksum, absent in a bare setting such as `$5$salt`.
+    pub checksum: Option<&'a str>,
+}
+
+impl<'a> Parsed<'a> {
+    /// Split a crypt string or setting into its fields.
+    pub fn parse(s: &'a str) -> Result<Self, &'static str> {
+        let rest = s.strip_prefix('$').ok_or("not a modular crypt string")?;
+        let (id, mut rest) = rest.split_once('$').ok_or("not a modular crypt string")?;
+        let scheme = Scheme::from_id(id).ok_or("unsupported scheme")?;
+
+        let mut rounds = None;
+        if let Some(after) = rest.strip_prefix("rounds=") {
+            let (n, after) = after.split_once('$').ok_or("invalid rounds")?;
+            if n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()) {
+                return Err("invalid rounds");
+            }
+            // Saturate overlong numbers; they clamp to ROUNDS_MAX anyway.
+            let n = n.parse::<u64>().unwrap_or(u64::MAX);
+            rounds = Some(n.clamp(ROUNDS_MIN as u64, ROUNDS_MAX as u64) as u32);
+            rest = after;
+        }
+
+        let (salt, checksum) = match rest.split_once('$') {
+            Some((salt, checksum)) => (salt, Some(checksum)),
+            None => (rest, None),
+        };
+        let salt = salt
+            .get(..salt.len().min(SALT_MAX))
+            .ok_or("salt must be ASCII")?;
+        if !salt.is_ascii() {
+            return Err("salt must be ASCII");
+        }
+        if let Some(c) = checksum
+            && (c.len() != scheme.checksum_len() || !c.bytes().all(|b| ALPHABET.contains(&b)))
+        {
+            return Err("malformed checksum");
+        }
+        Ok(Self {
+            scheme,
+            rounds,
+            salt,
+            checksum,
+        })
+    }
+}
+
+/// A formatted crypt string, held without allocating.
+#[derive(Clone, Copy)]
+pub struct CryptString {
+    buf: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl CryptString {
+    /// The whole string, e.g. `$5$salt$checksum`.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII is ever written.
+        ::core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    fn push(&mut self, s: &[u8]) {
+        self.buf[self.len..self.len + s.len()].copy_from_slice(s);
+        self.len += s.len();
+    }
+}
+
+impl ::core::fmt::Display for CryptString {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ::core::fmt::Debug for CryptString {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        ::core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Hash `password` into a new crypt string.
+///
+/// `rounds` of `None` uses [`ROUNDS_DEFAULT`] and leaves `rounds=` out of
+/// the string. Fails if `salt` is not ASCII or contains `$`.
+pub fn hash_password(
+    scheme: Scheme,
+    password: &[u8],
+    salt: &str,
+    rounds: Option<u32>,
+) -> Result<CryptString, &'static str> {
+    if !salt.is_ascii() || salt.contains('$') {
+        return Err("salt must be ASCII without '$'");
+    }
+    let rounds = rounds.map(|n| n.clamp(ROUNDS_MIN, ROUNDS_MAX));
+    Ok(format(
+        scheme,
+        password,
+        &salt[..salt.len().min(SALT_MAX)],
+        rounds,
+    ))
+}
+
+/// `crypt(3)`: hash `password` with the scheme, rounds and salt of
+/// `setting`, which may be a bare setting or a full crypt string.
+pub fn crypt(password: &[u8], setting: &str) -> Result<CryptString, &'static str> {
+    let parsed = Parsed::parse(setting)?;
+    Ok(format(parsed.scheme, password, parsed.salt, parsed.rounds))
+}
+
+/// Whether `password` matches the crypt string `hash`. The checksums are
+/// compared in constant time; malformed strings never match.
+pub fn verify(password: &[u8], hash: &str) -> bool {
+    let Ok(parsed) = Parsed::parse(hash) else {
+        return false;
+    };
+    let Some(expected) = parsed.checksum else {
+        return false;
+    };
+    let mut checksum = [0u8; 86];
+    let checksum = encode_checksum(
+        parsed.scheme,
+        password,
+        parsed.salt,
+        parsed.rounds,
+        &mut checksum,
+    );
+    ct_eq(checksum, expected.as_bytes())
+}
+
+fn format(scheme: Scheme, password: &[u8], salt: &str, rounds: Option<u32>) -> CryptString {
+    let mut out = CryptString {
+        buf: [0; MAX_LEN],
+        len: 0,
+    };
+    out.push(b"$");
+    out.push(scheme.id().as_bytes());
+    out.push(b"$");
+    if let Some(n) = rounds {
+        out.push(b"rounds=");
+        let mut digits = [0u8; 10];
+        let mut i = digits.len();
+        let mut n = n;
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        out.push(&digits[i..]);
+        out.push(b"$");
+    }
+    out.push(salt.as_bytes());
+    out.push(b"$");
+
+    let mut checksum = [0u8; 86];
+    out.push(encode_checksum(
+        scheme,
+        password,
+        salt,
+        rounds,
+        &mut checksum,
+    ));
+    out
+}
+
+/// The encoded checksum of `password`, written to the front of `out`.
+fn encode_checksum<'o>(
+    scheme: Scheme,
+    password: &[u8],
+    salt: &str,
+    rounds: Option<u32>,
+    out: &'o mut [u8; 86],
+) -> &'o [u8] {
+    let rounds = rounds.unwrap_or(ROUNDS_DEFAULT);
+    let salt = salt.as_bytes();
+    let mut w = Base64Writer { out, len: 0 };
+    match scheme {
+        #[cfg(feature = "sha256")]
+        Scheme::Sha256 => {
+            let d = digest::<Sha256Cfg, 64, 32>(password, salt, rounds);
+            for i in 0..10 {
+                let j = i * 21 % 30;
+                w.put(d[j], d[(j + 10) % 30], d[(j + 20) % 30], 4);
+            }
+            w.put(0, d[31], d[30], 3);
+        }
+        #[cfg(feature = "sha512")]
+        Scheme::Sha512 => {
+            let d = digest::<Sha512Cfg, 128, 64>(password, salt, rounds);
+            for i in 0..21 {
+                let j = i * 22 % 63;
+                w.put(d[j], d[(j + 21) % 63], d[(j + 42) % 63], 4);
+            }
+            w.put(0, 0, d[63], 2);
+        }
+    }
+    let len = w.len;
+    &w.out[..len]
+}
+
+/// The crypt base-64 encoding: three bytes per four characters, least
+/// significant six bits first.
+struct Base64Writer<'o> {
+    out: &'o mut [u8; 86],
+    len: usize,
+}
+
+impl Base64Writer<'_> {
+    fn put(&mut self, b2: u8, b1: u8, b0: u8, chars: usize) {
+        let mut w = (b2 as u32) << 16 | (b1 as u32) << 8 | b0 as u32;
+        for _ in 0..chars {
+            self.out[self.len] = ALPHABET[(w & 0x3f) as usize];
+            self.len += 1;
+            w >>= 6;
+        }
+    }
+}
+
+/// The SHA-crypt digest (steps 1 to 21 of the specification).
+fn digest<V: ShaVariant, const B: usize, const D: usize>(
+    password: &[u8],
+    salt: &[u8],
+    rounds: u32,
+) -> [u8; D] {
+    let new = ShaHasher::<V, B, D>::new;
+
+    let mut h = new();
+    h.update(password);
+    h.update(salt);
+    let alternate = h.finalize_with(password);
+
+    let mut h = new();
+    h.update(password);
+    h.update(salt);
+    update_repeated(&mut h, &alternate, password.len());
+    let mut n = password.len();
+    while n > 0 {
+        if n & 1 != 0 {
+            h.update(&alternate);
+        } else {
+            h.update(password);
+        }
+        n >>= 1;
+    }
+    let mut c = h.finalize();
+
+    // P and S sequences: digests of repeated password and salt, stretched
+    // or cut to the original lengths.
+    let mut h = new();
+    for _ in 0..password.len() {
+        h.update(password);
+    }
+    let dp = h.finalize();
+    let mut h = new();
+    for _ in 0..16 + c[0] as usize {
+        h.update(salt);
+    }
+    let ds = h.finalize();
+    let s_seq = &ds[..salt.len()];
+
+    for i in 0..rounds {
+        let mut h = new();
+        if i & 1 != 0 {
+            update_repeated(&mut h, &dp, password.len());
+        } else {
+            h.update(&c);
+        }
+        if i % 3 != 0 {
+            h.update(s_seq);
+        }
+        if i % 7 != 0 {
+            update_repeated(&mut h, &dp, password.len());
+        }
+        if i & 1 != 0 {
+            h.update(&c);
+        } else {
+            update_repeated(&mut h, &dp, password.len());
+        }
+        c = h.finalize();
+    }
+    c
+}
+
+/// Feed `block` repeated and cut to `len` bytes.
+fn update_repeated<V: ShaVariant, const B: usize, const D: usize>(
+    h: &mut ShaHasher<V, B, D>,
+    block: &[u8; D],
+    len: usize,
+) {
+    for _ in 0..len / D {
+        h.update(block);
+    }
+    h.update(&block[..len % D]);
+}
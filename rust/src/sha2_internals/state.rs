@@ -71,6 +71,22 @@ pub struct ShaState<V: ShaVariant, const BLOCK: usize, const SERIAL: usize> {
 impl<V: ShaVariant, const B: usize, const S: usize> ShaState<V, B, S> {
     pub const RAW_SIZE: usize = S;
 
+    /// Rejects `BLOCK`/`SERIAL` parameters that disagree with `V`'s family.
+    const PARAMS_OK: () = {
+        assert!(
+            B == <V::Family as ShaFamily>::BLOCK_BYTES,
+            "BLOCK does not match the variant's family"
+        );
+        assert!(
+            S == <V::Family as ShaFamily>::SERIAL_BYTES,
+            "SERIAL does not match the variant's family"
+        );
+        assert!(
+            V::BLOCK_BYTES == B && V::SERIAL_BYTES == S,
+            "BLOCK_BYTES and SERIAL_BYTES must not be overridden"
+        );
+    };
+
     /// Byte offset where the buffer region begins.
     #[inline]
     fn sb() -> usize {
@@ -112,6 +128,159 @@ impl<V: ShaVariant, const B: usize, const S: usize> ShaState<V, B, S> {
     }
 }
 
+// ---- compact encoding -----------------------------------------------------
+//
+// Layout (all big-endian except for the varint):
+//   `[0            .. STATE_BYTES]`                8 chaining-state words
+//   `[STATE_BYTES]`                                buffer_len  (`u8`)
+//   `[STATE_BYTES + 1 .. STATE_BYTES + 1 + n]`     `buffer[..buffer_len]`
+//   `[..]`                                         total_len (LEB128)
+
+#[cfg(all(
+    any(feature = "deserialize", feature = "serialize",),
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+impl<V: ShaVariant, const B: usize, const S: usize> ShaState<V, B, S> {
+    /// Upper bound on the length of the compact encoding
+    /// (106 for 32-bit families, 211 for 64-bit families).
+    pub const COMPACT_MAX_SIZE: usize = <V::Family as ShaFamily>::STATE_BYTES
+        + B
+        + (<V::Family as ShaFamily>::LEN_BYTES * 8).div_ceil(7);
+
+    /// Write the compact encoding into `out`, returning the number of
+    /// bytes written.
+    ///
+    /// Unlike [`raw`](Self::raw), only `buffer[..buffer_len]` is stored and
+    /// `total_len` is written as a LEB128 varint.
+    #[cfg(feature = "serialize")]
+    pub fn serialize_compact(&self, out: &mut [u8]) -> Result<usize, &'static str> {
+        let sb = Self::sb();
+        let buffer_len = self.buffer_len().ok_or("invalid buffer_len")?;
+        let mut varint = [0u8; 19];
+        let varint_len = write_varint(self.total_len(), &mut varint);
+        let size = sb + 1 + buffer_len + varint_len;
+
+        if out.len() < size {
+            return Err("output too small");
+        }
+
+        out[..sb].copy_from_slice(&self.inner[..sb]);
+        out[sb] = buffer_len as u8;
+        out[sb + 1..sb + 1 + buffer_len].copy_from_slice(&self.inner[sb..sb + buffer_len]);
+        out[sb + 1 + buffer_len..size].copy_from_slice(&varint[..varint_len]);
+
+        Ok(size)
+    }
+
+    /// Parse a compact encoding produced by
+    /// [`serialize_compact`](Self::serialize_compact).
+    ///
+    /// `src` must contain exactly one encoded state; trailing bytes,
+    /// non-canonical varints and lengths that do not fit the family's
+    /// length field are rejected.
+    #[cfg(feature = "deserialize")]
+    pub fn deserialize_compact(src: &[u8]) -> Result<Self, &'static str> {
+        let () = Self::PARAMS_OK;
+        let sb = Self::sb();
+        if src.len() <= sb {
+            return Err("truncated state");
+        }
+
+        let buffer_len = src[sb] as usize;
+        if buffer_len >= B {
+            return Err("invalid buffer_len");
+        }
+        let tail = src.get(sb + 1 + buffer_len..).ok_or("truncated state")?;
+
+        let (total_len, varint_len) = read_varint(tail).ok_or("invalid total_len")?;
+        if varint_len != tail.len() {
+            return Err("trailing data");
+        }
+        let len_bits = <V::Family as ShaFamily>::LEN_BYTES * 8;
+        if len_bits < 128 && total_len >> len_bits != 0 {
+            return Err("invalid total_len");
+        }
+
+        let mut inner = [0u8; S];
+        inner[..sb].copy_from_slice(&src[..sb]);
+        inner[sb..sb + buffer_len].copy_from_slice(&src[sb + 1..sb + 1 + buffer_len]);
+        inner[sb + B] = buffer_len as u8;
+        <V::Family>::write_len(total_len, &mut inner[sb + B + 1..]);
+
+        Ok(ShaState {
+            inner,
+            _variant: PhantomData,
+        })
+    }
+}
+
+/// Encode `value` as an unsigned LEB128 varint, returning its length.
+#[cfg(all(
+    feature = "serialize",
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+fn write_varint(mut value: u128, dst: &mut [u8; 19]) -> usize {
+    let mut i = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            dst[i] = byte;
+            return i + 1;
+        }
+        dst[i] = byte | 0x80;
+        i += 1;
+    }
+}
+
+/// Decode a canonical unsigned LEB128 varint, returning the value and the
+/// number of bytes consumed.
+#[cfg(all(
+    feature = "deserialize",
+    any(
+        feature = "sha224",
+        feature = "sha256",
+        feature = "sha384",
+        feature = "sha512",
+        feature = "sha512_224",
+        feature = "sha512_256"
+    )
+))]
+fn read_varint(src: &[u8]) -> Option<(u128, usize)> {
+    let mut value = 0u128;
+    for (i, &byte) in src.iter().enumerate().take(19) {
+        let bits = (byte & 0x7f) as u128;
+        let shift = 7 * i as u32;
+        // The 19th byte may only carry the two remaining bits of a u128.
+        if shift == 126 && bits > 0x03 {
+            return None;
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            // Reject overlong encodings (a trailing zero group).
+            if i > 0 && byte == 0 {
+                return None;
+            }
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
 // ---- From<&ShaHasher> → ShaState -----------------------------------------
 
 #[cfg(all(
@@ -129,6 +298,7 @@ impl<V: ShaVariant, const B: usize, const D: usize, const S: usize> From<&ShaHas
     for ShaState<V, B, S>
 {
     fn from(h: &ShaHasher<V, B, D>) -> Self {
+        let () = Self::PARAMS_OK;
         let sb = <V::Family as ShaFamily>::STATE_BYTES;
         let mut inner = [0u8; S];
 
@@ -182,6 +352,8 @@ impl<V: ShaVariant, const B: usize, const D: usize, const S: usize> TryFrom<&Sha
     type Error = &'static str;
 
     fn try_from(s: &ShaState<V, B, S>) -> Result<Self, Self::Error> {
+        let () = ShaState::<V, B, S>::PARAMS_OK;
+        let () = Self::PARAMS_OK;
         let buffer_len = s.buffer_len().ok_or("invalid buffer_len")?;
         Ok(ShaHasher {
             engine: ShaEngine::new(s.state()),
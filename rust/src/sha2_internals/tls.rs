@@ -0,0 +1,400 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+//! TLS key derivation: the TLS 1.2 PRF (RFC 5246 section 5) and the
+//! TLS 1.3 key schedule (RFC 8446 section 7).
+//!
+//! The hash is fixed by the variant. A TLS 1.3 handshake keeps one
+//! [`Transcript`] and takes a snapshot of it wherever the key schedule
+//! needs a transcript hash, e.g.
+//!
+//! ```
+//! # #[cfg(feature = "sha256")] {
+//! use sha2::tls::{KeySchedule, Transcript};
+//! use sha2::Sha256Cfg;
+//!
+//! # let (client_hello, server_hello, ecdhe) = (b"CH", b"SH", [0x42u8; 32]);
+//! let mut transcript = Transcript::<Sha256Cfg, 64, 32>::new();
+//! let mut schedule = KeySchedule::<Sha256Cfg, 64, 32>::new(None);
+//!
+//! transcript.update(client_hello);
+//! transcript.update(server_hello);
+//! schedule.handshake(Some(&ecdhe)).unwrap();
+//! let server_hs = schedule
+//!     .server_handshake_traffic_secret(&transcript.hash())
+//!     .unwrap();
+//!
+//! let (mut key, mut iv) = ([0u8; 16], [0u8; 12]);
+//! KeySchedule::<Sha256Cfg, 64, 32>::traffic_key_iv(&server_hs, &mut key, &mut iv).unwrap();
+//! # }
+//! ```
+
+use super::{hasher::ShaHasher, hkdf::Hkdf, hmac::Hmac, variant::ShaVariant};
+
+// ---- TLS 1.2 ---------------------------------------------------------------
+
+/// `P_hash(secret, seed)`, the data expansion function of TLS 1.2, written
+/// to fill `out`.
+pub fn p_hash<V: ShaVariant, const B: usize, const D: usize>(
+    secret: &[u8],
+    seed: &[u8],
+    out: &mut [u8],
+) {
+    p_hash_parts::<V, B, D>(secret, &[seed], out);
+}
+
+/// The TLS 1.2 `PRF(secret, label, seed)`: `P_hash` over `label || seed`.
+///
+/// Cipher suites name their PRF hash; most use SHA-256, the
+/// `_SHA384` suites SHA-384.
+pub fn prf<V: ShaVariant, const B: usize, const D: usize>(
+    secret: &[u8],
+    label: &[u8],
+    seed: &[u8],
+    out: &mut [u8],
+) {
+    p_hash_parts::<V, B, D>(secret, &[label, seed], out);
+}
+
+/// `P_hash` with the seed given as the concatenation of `seed`.
+fn p_hash_parts<V: ShaVariant, const B: usize, const D: usize>(
+    secret: &[u8],
+    seed: &[&[u8]],
+    out: &mut [u8],
+) {
+    // `finalize` leaves the MAC keyed, so one instance serves every call.
+    let mut mac = Hmac::<V, B, D>::new(secret);
+    for part in seed {
+        mac.update(part);
+    }
+    let mut a = mac.finalize();
+
+    for chunk in out.chunks_mut(D) {
+        mac.update(&a);
+        for part in seed {
+            mac.update(part);
+        }
+        chunk.copy_from_slice(&mac.finalize()[..chunk.len()]);
+
+        mac.update(&a);
+        a = mac.finalize();
+    }
+}
+
+// ---- TLS 1.3 ---------------------------------------------------------------
+
+/// Longest label accepted by [`hkdf_expand_label`], leaving room for the
+/// `"tls13 "` prefix.
+pub const MAX_LABEL_BYTES: usize = 255 - 6;
+
+/// `HKDF-Expand-Label(secret, label, context, out.len())`. The `"tls13 "`
+/// prefix is added here; `label` is the bare label, e.g. `b"key"`.
+///
+/// Fails if `label` is longer than [`MAX_LABEL_BYTES`], `context` longer
+/// than 255 bytes or `out` longer than 255 hash lengths.
+pub fn hkdf_expand_label<V: ShaVariant, const B: usize, const D: usize>(
+    secret: &[u8; D],
+    label: &[u8],
+    context: &[u8],
+    out: &mut [u8],
+) -> Result<(), &'static str> {
+    if label.len() > MAX_LABEL_BYTES {
+        return Err("label too long");
+    }
+    if context.len() > 255 {
+        return Err("context too long");
+    }
+    // HKDF-Expand caps `out` far below 2^16 bytes, so the length fits.
+    let length = (out.len().min(u16::MAX as usize) as u16).to_be_bytes();
+    Hkdf::<V, B, D>::from_prk(secret).expand_parts(
+        &[
+            &length,
+            &[(6 + label.len()) as u8],
+            b"tls13 ",
+            label,
+            &[context.len() as u8],
+            context,
+        ],
+        out,
+    )
+}
+
+/// `Derive-Secret(secret, label, messages)`, given the transcript hash of
+/// `messages`.
+///
+/// Fails if `label` is longer than [`MAX_LABEL_BYTES`].
+pub fn derive_secret<V: ShaVariant, const B: usize, const D: usize>(
+    secret: &[u8; D],
+    label: &[u8],
+    transcript_hash: &[u8; D],
+) -> Result<[u8; D], &'static str> {
+    let mut out = [0u8; D];
+    hkdf_expand_label::<V, B, D>(secret, label, transcript_hash, &mut out)?;
+    Ok(out)
+}
+
+/// The running hash of the handshake messages.
+///
+/// [`hash`](Self::hash) forks the hasher state, so the transcript can be
+/// snapshotted at each point the key schedule needs and keep growing.
+pub struct Transcript<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    hasher: ShaHasher<V, BLOCK, DIGEST>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Clone for Transcript<V, B, D> {
+    fn clone(&self) -> Self {
+        Self {
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Default for Transcript<V, B, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> Transcript<V, B, D> {
+    /// An empty transcript.
+    pub fn new() -> Self {
+        Self {
+            hasher: ShaHasher::new(),
+        }
+    }
+
+    /// Append a handshake message, including its four-byte header.
+    #[inline]
+    pub fn update(&mut self, message: &[u8]) {
+        self.hasher.update(message);
+    }
+
+    /// The hash of every message so far. The transcript is unchanged.
+    pub fn hash(&self) -> [u8; D] {
+        self.hasher.clone().finalize()
+    }
+}
+
+/// Position in the TLS 1.3 key schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// After the early secret, before any (EC)DHE input.
+    Early,
+    /// After the handshake secret.
+    Handshake,
+    /// After the master secret.
+    Master,
+}
+
+/// The TLS 1.3 key schedule, moving from the early secret through the
+/// handshake secret to the master secret.
+///
+/// Each secret is only derivable at its own stage; asking for one at
+/// another stage fails with `"wrong key schedule stage"`.
+pub struct KeySchedule<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    secret: [u8; DIGEST],
+    stage: Stage,
+    _variant: ::core::marker::PhantomData<V>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> KeySchedule<V, B, D> {
+    /// Start from the early secret. Without a PSK, `DIGEST` zero bytes are
+    /// used in its place.
+    pub fn new(psk: Option<&[u8]>) -> Self {
+        let zeros = [0u8; D];
+        Self {
+            secret: *Hkdf::<V, B, D>::extract(&[], psk.unwrap_or(&zeros)).prk(),
+            stage: Stage::Early,
+            _variant: ::core::marker::PhantomData,
+        }
+    }
+
+    /// The current stage.
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// The secret of the current stage.
+    pub fn secret(&self) -> &[u8; D] {
+        &self.secret
+    }
+
+    /// The PSK binder key: `"ext binder"` for an external PSK, otherwise
+    /// `"res binder"`.
+    pub fn binder_key(&self, external: bool) -> Result<[u8; D], &'static str> {
+        let label: &[u8] = if external {
+            b"ext binder"
+        } else {
+            b"res binder"
+        };
+        self.derive(Stage::Early, label, &empty_hash::<V, B, D>())
+    }
+
+    /// `client_early_traffic_secret`, over ClientHello.
+    pub fn client_early_traffic_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Early, b"c e traffic", transcript_hash)
+    }
+
+    /// `early_exporter_master_secret`, over ClientHello.
+    pub fn early_exporter_master_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Early, b"e exp master", transcript_hash)
+    }
+
+    /// Move to the handshake secret, mixing in the (EC)DHE shared secret.
+    /// PSK-only handshakes pass `None`, standing for `DIGEST` zero bytes.
+    pub fn handshake(&mut self, shared_secret: Option<&[u8]>) -> Result<(), &'static str> {
+        self.advance(Stage::Early, Stage::Handshake, shared_secret)
+    }
+
+    /// `client_handshake_traffic_secret`, over ClientHello...ServerHello.
+    pub fn client_handshake_traffic_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Handshake, b"c hs traffic", transcript_hash)
+    }
+
+    /// `server_handshake_traffic_secret`, over ClientHello...ServerHello.
+    pub fn server_handshake_traffic_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Handshake, b"s hs traffic", transcript_hash)
+    }
+
+    /// Move to the master secret.
+    pub fn master(&mut self) -> Result<(), &'static str> {
+        self.advance(Stage::Handshake, Stage::Master, None)
+    }
+
+    /// `client_application_traffic_secret_0`, over ClientHello...server
+    /// Finished.
+    pub fn client_application_traffic_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Master, b"c ap traffic", transcript_hash)
+    }
+
+    /// `server_application_traffic_secret_0`, over ClientHello...server
+    /// Finished.
+    pub fn server_application_traffic_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Master, b"s ap traffic", transcript_hash)
+    }
+
+    /// `exporter_master_secret`, over ClientHello...server Finished.
+    pub fn exporter_master_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Master, b"exp master", transcript_hash)
+    }
+
+    /// `resumption_master_secret`, over ClientHello...client Finished.
+    pub fn resumption_master_secret(
+        &self,
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        self.derive(Stage::Master, b"res master", transcript_hash)
+    }
+
+    /// The record protection key and IV for a traffic secret, sized by
+    /// `key` and `iv`.
+    pub fn traffic_key_iv(
+        secret: &[u8; D],
+        key: &mut [u8],
+        iv: &mut [u8],
+    ) -> Result<(), &'static str> {
+        hkdf_expand_label::<V, B, D>(secret, b"key", &[], key)?;
+        hkdf_expand_label::<V, B, D>(secret, b"iv", &[], iv)
+    }
+
+    /// The key a Finished message is computed under, from the sender's
+    /// handshake traffic secret.
+    pub fn finished_key(base_key: &[u8; D]) -> [u8; D] {
+        Self::expand_fixed(base_key, b"finished", &[])
+    }
+
+    /// Finished `verify_data` for the sender's handshake traffic secret,
+    /// over the transcript up to but excluding the Finished message.
+    pub fn finished_verify_data(base_key: &[u8; D], transcript_hash: &[u8; D]) -> [u8; D] {
+        Hmac::<V, B, D>::mac(&Self::finished_key(base_key), transcript_hash)
+    }
+
+    /// `application_traffic_secret_N+1` after a KeyUpdate.
+    pub fn next_traffic_secret(secret: &[u8; D]) -> [u8; D] {
+        Self::expand_fixed(secret, b"traffic upd", &[])
+    }
+
+    /// The PSK for a NewSessionTicket with `ticket_nonce`.
+    ///
+    /// Fails if `ticket_nonce` is longer than 255 bytes.
+    pub fn resumption_psk(
+        resumption_master_secret: &[u8; D],
+        ticket_nonce: &[u8],
+    ) -> Result<[u8; D], &'static str> {
+        let mut psk = [0u8; D];
+        hkdf_expand_label::<V, B, D>(
+            resumption_master_secret,
+            b"resumption",
+            ticket_nonce,
+            &mut psk,
+        )?;
+        Ok(psk)
+    }
+
+    fn derive(
+        &self,
+        stage: Stage,
+        label: &[u8],
+        transcript_hash: &[u8; D],
+    ) -> Result<[u8; D], &'static str> {
+        if self.stage != stage {
+            return Err("wrong key schedule stage");
+        }
+        Ok(Self::expand_fixed(&self.secret, label, transcript_hash))
+    }
+
+    fn advance(&mut self, from: Stage, to: Stage, ikm: Option<&[u8]>) -> Result<(), &'static str> {
+        let derived = self.derive(from, b"derived", &empty_hash::<V, B, D>())?;
+        let zeros = [0u8; D];
+        self.secret = *Hkdf::<V, B, D>::extract(&derived, ikm.unwrap_or(&zeros)).prk();
+        self.stage = to;
+        Ok(())
+    }
+
+    /// HKDF-Expand-Label to one hash length, for the schedule's own short
+    /// labels and contexts.
+    fn expand_fixed(secret: &[u8; D], label: &[u8], context: &[u8]) -> [u8; D] {
+        let mut out = [0u8; D];
+        hkdf_expand_label::<V, B, D>(secret, label, context, &mut out)
+            .expect("fixed labels and contexts are within limits");
+        out
+    }
+}
+
+/// The hash of an empty transcript.
+fn empty_hash<V: ShaVariant, const B: usize, const D: usize>() -> [u8; D] {
+    ShaHasher::<V, B, D>::new().finalize()
+}
@@ -0,0 +1,255 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+//! Tree hashing: splits the input into fixed-size chunks, hashes each chunk
+//! as a leaf and combines the leaf digests through `fanout`-ary inner nodes.
+//!
+//! Unlike plain SHA-2, leaves can be hashed independently, so large inputs
+//! can use every core (see the `rayon` feature). The root is a different
+//! value from the plain SHA-2 digest of the same input.
+//!
+//! # Construction
+//!
+//! * The input is cut into chunks of `chunk_size` bytes; the last chunk may
+//!   be shorter. The empty input is a single empty chunk.
+//! * Leaf: `H(0x00 || chunk)`.
+//! * Inner node: `H(0x01 || child_0 || … || child_k)`, for up to `fanout`
+//!   children.
+//! * Each level is grouped left to right into runs of `fanout` nodes (the
+//!   last run may be shorter) until a single node, the root, remains.
+
+use ::std::vec::Vec;
+
+use super::{hasher::ShaHasher, variant::ShaVariant};
+
+/// Domain-separation prefix of a leaf node.
+const LEAF: u8 = 0x00;
+/// Domain-separation prefix of an inner node.
+const INNER: u8 = 0x01;
+
+/// Shape of the hash tree.
+///
+/// Both values are part of the result: the same input hashed with
+/// different parameters gives unrelated roots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeParams {
+    /// Leaf size in bytes. Must be non-zero.
+    pub chunk_size: usize,
+    /// Maximum number of children per inner node. Must be at least 2.
+    pub fanout: usize,
+}
+
+impl Default for TreeParams {
+    /// 64 KiB chunks, 16 children per inner node.
+    fn default() -> Self {
+        Self {
+            chunk_size: 64 * 1024,
+            fanout: 16,
+        }
+    }
+}
+
+impl TreeParams {
+    fn check(&self) -> Result<(), &'static str> {
+        if self.chunk_size == 0 {
+            return Err("chunk_size must be non-zero");
+        }
+        if self.fanout < 2 {
+            return Err("fanout must be at least 2");
+        }
+        Ok(())
+    }
+}
+
+/// Incremental tree hasher on top of [`ShaHasher`].
+///
+/// Parameters mirror [`ShaHasher`]; use the `Sha256Tree`-style aliases
+/// instead of spelling them out.
+///
+/// Completed subtrees are folded as soon as they fill up, so memory use is
+/// `O(fanout · log_fanout(chunks))` digests regardless of input size. With
+/// the `rayon` feature, the whole chunks of each `update` call are hashed
+/// in parallel.
+pub struct TreeHasher<V: ShaVariant, const BLOCK: usize, const DIGEST: usize> {
+    params: TreeParams,
+    leaf: ShaHasher<V, BLOCK, DIGEST>,
+    leaf_len: usize,
+    /// Pending digests per level, each holding fewer than `fanout` entries.
+    /// A level exists once a node has been pushed to it.
+    levels: Vec<Vec<[u8; DIGEST]>>,
+}
+
+impl<V: ShaVariant, const B: usize, const D: usize> TreeHasher<V, B, D> {
+    /// Create a tree hasher with the given shape.
+    pub fn new(params: TreeParams) -> Result<Self, &'static str> {
+        params.check()?;
+        Ok(Self {
+            params,
+            leaf: Self::leaf_hasher(),
+            leaf_len: 0,
+            levels: Vec::new(),
+        })
+    }
+
+    /// The shape this hasher was created with.
+    pub fn params(&self) -> TreeParams {
+        self.params
+    }
+
+    /// Reset to the empty input, keeping the parameters.
+    pub fn reset(&mut self) {
+        self.leaf = Self::leaf_hasher();
+        self.leaf_len = 0;
+        self.levels.clear();
+    }
+
+    /// Feed data into the hasher.  May be called repeatedly.
+    pub fn update(&mut self, mut data: &[u8]) {
+        let chunk_size = self.params.chunk_size;
+
+        while !data.is_empty() {
+            // The open leaf is only closed once more input shows it is not
+            // the last one.
+            if self.leaf_len == chunk_size {
+                self.close_leaf();
+            }
+
+            if self.leaf_len == 0 && data.len() > chunk_size {
+                // Whole chunks followed by more input: hash them directly,
+                // keeping at least one byte for the open leaf.
+                let whole = (data.len() - 1) / chunk_size * chunk_size;
+                let (chunks, rest) = data.split_at(whole);
+                for digest in Self::hash_leaves(chunks, chunk_size) {
+                    self.push(0, digest);
+                }
+                data = rest;
+                continue;
+            }
+
+            let take = (chunk_size - self.leaf_len).min(data.len());
+            self.leaf.update(&data[..take]);
+            self.leaf_len += take;
+            data = &data[take..];
+        }
+    }
+
+    /// Return the root digest and reset the hasher.
+    pub fn finalize(&mut self) -> [u8; D] {
+        self.close_leaf();
+
+        let fanout = self.params.fanout;
+        let top = self.levels.len() - 1;
+        let mut carry = None;
+        let mut root = [0u8; D];
+
+        for (i, level) in self.levels.iter_mut().enumerate() {
+            level.extend(carry.take());
+            if i == top {
+                // Nothing has been folded out of the top level, so what is
+                // left here is the whole level.
+                root = match level.as_slice() {
+                    [only] => *only,
+                    nodes => Self::inner(nodes),
+                };
+            } else if !level.is_empty() {
+                debug_assert!(level.len() <= fanout);
+                carry = Some(Self::inner(level));
+            }
+        }
+
+        self.reset();
+        root
+    }
+
+    /// One-shot tree hash; parallel with the `rayon` feature.
+    pub fn digest(params: TreeParams, data: &[u8]) -> Result<[u8; D], &'static str> {
+        let mut h = Self::new(params)?;
+        h.update(data);
+        Ok(h.finalize())
+    }
+
+    /// Straightforward level-by-level reference implementation of the
+    /// construction. Always sequential; produces the same root as
+    /// [`digest`](Self::digest) and the incremental interface.
+    pub fn digest_sequential(params: TreeParams, data: &[u8]) -> Result<[u8; D], &'static str> {
+        params.check()?;
+
+        let mut level: Vec<[u8; D]> = if data.is_empty() {
+            ::std::vec![Self::leaf(data)]
+        } else {
+            data.chunks(params.chunk_size).map(Self::leaf).collect()
+        };
+        while level.len() > 1 {
+            level = level.chunks(params.fanout).map(Self::inner).collect();
+        }
+
+        Ok(level[0])
+    }
+
+    fn leaf_hasher() -> ShaHasher<V, B, D> {
+        let mut h = ShaHasher::new();
+        h.update(&[LEAF]);
+        h
+    }
+
+    fn leaf(chunk: &[u8]) -> [u8; D] {
+        let mut h = Self::leaf_hasher();
+        h.update(chunk);
+        h.finalize()
+    }
+
+    fn inner(children: &[[u8; D]]) -> [u8; D] {
+        let mut h = ShaHasher::<V, B, D>::new();
+        h.update(&[INNER]);
+        for child in children {
+            h.update(child);
+        }
+        h.finalize()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn hash_leaves(chunks: &[u8], chunk_size: usize) -> Vec<[u8; D]> {
+        use ::rayon::prelude::*;
+
+        chunks.par_chunks(chunk_size).map(Self::leaf).collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn hash_leaves(chunks: &[u8], chunk_size: usize) -> impl Iterator<Item = [u8; D]> {
+        chunks.chunks(chunk_size).map(Self::leaf)
+    }
+
+    fn close_leaf(&mut self) {
+        let digest = self.leaf.finalize();
+        self.leaf = Self::leaf_hasher();
+        self.leaf_len = 0;
+        self.push(0, digest);
+    }
+
+    /// Append a node to `level`, folding the level into its parent once it
+    /// holds `fanout` nodes.
+    fn push(&mut self, level: usize, digest: [u8; D]) {
+        if level == self.levels.len() {
+            self.levels.push(Vec::with_capacity(self.params.fanout));
+        }
+
+        let nodes = &mut self.levels[level];
+        nodes.push(digest);
+        if nodes.len() == self.params.fanout {
+            let parent = Self::inner(nodes);
+            nodes.clear();
+            self.push(level + 1, parent);
+        }
+    }
+}
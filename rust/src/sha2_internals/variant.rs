@@ -35,6 +35,34 @@ use super::family::ShaFamily;
 
 /// A specific SHA-2 algorithm. Two constants over a shared
 /// [`ShaFamily`] fully define a variant.
+///
+/// Downstream crates may implement this trait to declare their own
+/// variants, e.g. a domain-separated SHA-256 with a personalised IV:
+///
+/// ```
+/// # #[cfg(feature = "sha256")] {
+/// use sha2::{Sha2_32, ShaHasher, ShaVariant};
+///
+/// struct TaggedCfg;
+/// impl ShaVariant for TaggedCfg {
+///     type Family = Sha2_32;
+///     const IV: [u32; 8] = [
+///         0x0ee5ee7c, 0x5ae6c1f9, 0x3c1a4bb6, 0x8e7fd6a2, 0x9f6c1e4b, 0x2b8f5d04, 0x6d2a0c57,
+///         0x1f3b8a90,
+///     ];
+///     const DIGEST_BYTES: usize = 20;
+/// }
+///
+/// type Tagged = ShaHasher<TaggedCfg, { TaggedCfg::BLOCK_BYTES }, { TaggedCfg::DIGEST_BYTES }>;
+///
+/// let mut h = Tagged::new();
+/// h.update(b"message");
+/// let _digest: [u8; 20] = h.finalize();
+/// # }
+/// ```
+///
+/// `DIGEST_BYTES` may be anything up to the family's state size; the
+/// digest is the leading bytes of the final chaining state.
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -49,6 +77,17 @@ pub trait ShaVariant: 'static {
     const IV: [<Self::Family as ShaFamily>::Word; 8];
     /// Digest output length in bytes.
     const DIGEST_BYTES: usize;
+
+    /// Block size in bytes, as fixed by the family. Not to be overridden:
+    /// hashers with any other value fail to compile.
+    const BLOCK_BYTES: usize = <Self::Family as ShaFamily>::BLOCK_BYTES;
+    /// Serialised checkpoint size in bytes, as fixed by the family. Not to
+    /// be overridden either.
+    const SERIAL_BYTES: usize = <Self::Family as ShaFamily>::SERIAL_BYTES;
+    /// DER `DigestInfo` header that precedes the digest in PKCS #1 v1.5
+    /// signatures (RFC 8017, section 9.2). Empty for variants without an
+    /// assigned object identifier.
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[];
 }
 
 // ---- 32-bit variants -----------------------------------------------------
@@ -63,6 +102,10 @@ impl ShaVariant for Sha256Cfg {
         0x5be0cd19,
     ];
     const DIGEST_BYTES: usize = 32;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        0x05, 0x00, 0x04, 0x20,
+    ];
 }
 
 #[cfg(feature = "sha224")]
@@ -75,6 +118,10 @@ impl ShaVariant for Sha224Cfg {
         0xbefa4fa4,
     ];
     const DIGEST_BYTES: usize = 28;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04,
+        0x05, 0x00, 0x04, 0x1c,
+    ];
 }
 
 // ---- 64-bit variants -----------------------------------------------------
@@ -95,6 +142,10 @@ impl ShaVariant for Sha512Cfg {
         0x5be0cd19137e2179,
     ];
     const DIGEST_BYTES: usize = 64;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+        0x05, 0x00, 0x04, 0x40,
+    ];
 }
 
 #[cfg(feature = "sha384")]
@@ -113,6 +164,10 @@ impl ShaVariant for Sha384Cfg {
         0x47b5481dbefa4fa4,
     ];
     const DIGEST_BYTES: usize = 48;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02,
+        0x05, 0x00, 0x04, 0x30,
+    ];
 }
 
 #[cfg(feature = "sha512_224")]
@@ -131,6 +186,10 @@ impl ShaVariant for Sha512_224Cfg {
         0x1112e6ad91d692a1,
     ];
     const DIGEST_BYTES: usize = 28;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x05,
+        0x05, 0x00, 0x04, 0x1c,
+    ];
 }
 
 #[cfg(feature = "sha512_256")]
@@ -149,4 +208,8 @@ impl ShaVariant for Sha512_256Cfg {
         0x0eb72ddc81c52ca2,
     ];
     const DIGEST_BYTES: usize = 32;
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x06,
+        0x05, 0x00, 0x04, 0x20,
+    ];
 }
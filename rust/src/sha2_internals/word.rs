@@ -21,9 +21,14 @@
     feature = "sha512_224",
     feature = "sha512_256"
 ))]
-use ::core::ops::{BitAnd, BitOr, BitXor, Not, Shr};
+use {
+    super::sealed::Sealed,
+    ::core::ops::{BitAnd, BitOr, BitXor, Not, Shr},
+};
 
 /// Abstracts over the SHA-2 word types (`u32` and `u64`).
+///
+/// This trait is sealed.
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -33,7 +38,8 @@ use ::core::ops::{BitAnd, BitOr, BitXor, Not, Shr};
     feature = "sha512_256"
 ))]
 pub trait ShaWord:
-    Copy
+    Sealed
+    + Copy
     + Clone
     + Default
     + Eq
@@ -53,6 +59,16 @@ pub trait ShaWord:
     fn wrapping_add(self, rhs: Self) -> Self;
 }
 
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+impl Sealed for u32 {}
+
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -89,6 +105,16 @@ impl ShaWord for u32 {
     }
 }
 
+#[cfg(any(
+    feature = "sha224",
+    feature = "sha256",
+    feature = "sha384",
+    feature = "sha512",
+    feature = "sha512_224",
+    feature = "sha512_256"
+))]
+impl Sealed for u64 {}
+
 #[cfg(any(
     feature = "sha224",
     feature = "sha256",
@@ -0,0 +1,177 @@
+/* Copyright © 2026 Apeleg Limited. All rights reserved.
+ *
+ * Permission to use, copy, modify, and distribute this software for any
+ * purpose with or without fee is hereby granted, provided that the above
+ * copyright notice and this permission notice appear in all copies.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES WITH
+ * REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF MERCHANTABILITY
+ * AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY SPECIAL, DIRECT,
+ * INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER RESULTING FROM
+ * LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF CONTRACT, NEGLIGENCE OR
+ * OTHER TORTIOUS ACTION, ARISING OUT OF OR IN CONNECTION WITH THE USE OR
+ * PERFORMANCE OF THIS SOFTWARE.
+ */
+
+/**
+ * @file Checks the `sha256_digest` and `sha256_digest_batch` exports of
+ * `wasm-sha2` against `node:crypto`, built for `wasm32v1-none` once as the
+ * scalar unrolled module and once with SIMD128, which runs the SIMD128
+ * message schedule and the four-lane batch hasher.
+ *
+ * Run from the workspace root with `node --test wasm/test/simd128.test.mjs`.
+ */
+
+import assert from 'node:assert/strict';
+import { execFileSync } from 'node:child_process';
+import { createHash } from 'node:crypto';
+import { readFileSync } from 'node:fs';
+import { dirname, join, resolve } from 'node:path';
+import process from 'node:process';
+import { describe, test } from 'node:test';
+import { fileURLToPath } from 'node:url';
+
+const WORKSPACE_DIR = resolve(dirname(fileURLToPath(import.meta.url)), '../..');
+const TARGET = 'wasm32v1-none';
+const DIGEST_BYTES = 32;
+
+const VARIANTS = [
+	{ name: 'scalar', features: [], rustflags: [] },
+	{
+		name: 'simd128',
+		features: ['simd128'],
+		rustflags: ['-Ctarget-feature=+simd128'],
+	},
+];
+
+/** Build `wasm-sha2` for one variant and instantiate it. */
+const instantiate = async (variant) => {
+	const targetDir = join(
+		WORKSPACE_DIR,
+		'target',
+		`wasm-test-${variant.name}`,
+	);
+	execFileSync(
+		'cargo',
+		[
+			'build',
+			'--frozen',
+			'--lib',
+			'--release',
+			'--target',
+			TARGET,
+			'--package',
+			'wasm-sha2',
+			'--target-dir',
+			targetDir,
+			'--no-default-features',
+			'--features',
+			[
+				'sha256',
+				'sync',
+				'sha2-compress-unrolled',
+				...variant.features,
+			].join(','),
+		],
+		{
+			cwd: WORKSPACE_DIR,
+			env: { ...process.env, RUSTFLAGS: variant.rustflags.join(' ') },
+			stdio: 'inherit',
+		},
+	);
+	const wasm = readFileSync(
+		join(targetDir, TARGET, 'release', 'wasm_sha2.wasm'),
+	);
+	const { instance } = await WebAssembly.instantiate(wasm);
+	return instance.exports;
+};
+
+/** Deterministic test message of `len` bytes. */
+const message = (len, seed) =>
+	Uint8Array.from({ length: len }, (_, i) => (i * 31 + seed * 7 + 1) & 0xff);
+
+const sha256 = (msg) =>
+	new Uint8Array(createHash('sha256').update(msg).digest());
+
+/** Bump allocator over pages grown past the module's own memory. */
+const heap = (exports) => {
+	let next = exports.memory.grow(32) * 65536;
+	return (bytes) => {
+		const at = next;
+		next += (bytes + 15) & ~15;
+		return at;
+	};
+};
+
+// Around the one- and two-block padding boundaries, plus longer inputs.
+const LENGTHS = [0, 1, 55, 56, 63, 64, 65, 119, 120, 128, 1000, 4103];
+
+for (const variant of VARIANTS) {
+	describe(`wasm-sha2 (${variant.name})`, async () => {
+		const exports = await instantiate(variant);
+
+		test('sha256_digest', () => {
+			const alloc = heap(exports);
+			const out = alloc(DIGEST_BYTES);
+			for (const len of LENGTHS) {
+				const msg = message(len, 0);
+				const ptr = alloc(len);
+				new Uint8Array(exports.memory.buffer, ptr, len).set(msg);
+				assert.equal(
+					exports.sha256_digest(out, DIGEST_BYTES, ptr, len),
+					DIGEST_BYTES,
+				);
+				assert.deepStrictEqual(
+					new Uint8Array(exports.memory.buffer, out, DIGEST_BYTES),
+					sha256(msg),
+					`len = ${len}`,
+				);
+			}
+		});
+
+		test('sha256_digest_batch', () => {
+			// Partial and full groups of eight, with lanes of different
+			// block counts in each group.
+			for (const count of [1, 3, 8, 9, 17]) {
+				const alloc = heap(exports);
+				const msgs = Array.from({ length: count }, (_, i) =>
+					message(LENGTHS[(i * 5) % LENGTHS.length], i),
+				);
+				const ptrs = alloc(4 * count);
+				const sizes = alloc(4 * count);
+				const out = alloc(DIGEST_BYTES * count);
+				const view = new DataView(exports.memory.buffer);
+				msgs.forEach((msg, i) => {
+					const ptr = alloc(msg.length);
+					new Uint8Array(exports.memory.buffer, ptr, msg.length).set(
+						msg,
+					);
+					view.setUint32(ptrs + 4 * i, ptr, true);
+					view.setUint32(sizes + 4 * i, msg.length, true);
+				});
+
+				assert.equal(
+					exports.sha256_digest_batch(
+						out,
+						DIGEST_BYTES * count,
+						ptrs,
+						sizes,
+						count,
+					),
+					DIGEST_BYTES * count,
+				);
+				msgs.forEach((msg, i) => {
+					assert.deepStrictEqual(
+						new Uint8Array(
+							exports.memory.buffer,
+							out + DIGEST_BYTES * i,
+							DIGEST_BYTES,
+						),
+						sha256(msg),
+						`count = ${count}, message ${i}`,
+					);
+				});
+			}
+		});
+	});
+}
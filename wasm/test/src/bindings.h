@@ -86,6 +86,13 @@ uintptr_t sha224_finalize(Sha224 *ctx, uint8_t *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha224_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha224_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
 /**
  * # Safety
  *
@@ -100,6 +107,20 @@ uintptr_t sha224_serialize(Sha224State *result_ptr, uintptr_t result_size, const
  */
 uintptr_t sha224_deserialize(Sha224 *ctx, uintptr_t ctx_size, const Sha224State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha224_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha224 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha224_deserialize_compact(Sha224 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 /**
  * # Safety
  *
@@ -135,6 +156,14 @@ uintptr_t sha256_finalize(Sha256 *ctx, uint8_t *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha256_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha256_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
+
 /**
  * # Safety
  *
@@ -149,6 +178,20 @@ uintptr_t sha256_serialize(Sha256State *result_ptr, uintptr_t result_size, const
  */
 uintptr_t sha256_deserialize(Sha256 *ctx, uintptr_t ctx_size, const Sha256State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha256_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha256 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha256_deserialize_compact(Sha256 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 /**
  * # Safety
  *
@@ -184,6 +227,13 @@ uintptr_t sha384_finalize(Sha384 *ctx, uint8_t *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha384_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha384_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
 /**
  * # Safety
  *
@@ -198,6 +248,20 @@ uintptr_t sha384_serialize(Sha384State *result_ptr, uintptr_t result_size, const
  */
 uintptr_t sha384_deserialize(Sha384 *ctx, uintptr_t ctx_size, const Sha384State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha384_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha384 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha384_deserialize_compact(Sha384 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 /**
  * # Safety
  *
@@ -233,6 +297,13 @@ uintptr_t sha512_finalize(Sha512 *ctx, uint8_t *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha512_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
 /**
  * # Safety
  *
@@ -247,6 +318,20 @@ uintptr_t sha512_serialize(Sha512State *result_ptr, uintptr_t result_size, const
  */
 uintptr_t sha512_deserialize(Sha512 *ctx, uintptr_t ctx_size, const Sha512State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha512 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_deserialize_compact(Sha512 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 /**
  * # Safety
  *
@@ -282,6 +367,13 @@ uintptr_t sha512_224_finalize(Sha512_224 *ctx, uint8_t *result_ptr, uintptr_t re
  */
 uintptr_t sha512_224_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_224_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
 /**
  * # Safety
  *
@@ -296,6 +388,20 @@ uintptr_t sha512_224_serialize(Sha512_224State *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha512_224_deserialize(Sha512_224 *ctx, uintptr_t ctx_size, const Sha512_224State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_224_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha512_224 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_224_deserialize_compact(Sha512_224 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 /**
  * # Safety
  *
@@ -331,6 +437,13 @@ uintptr_t sha512_256_finalize(Sha512_256 *ctx, uint8_t *result_ptr, uintptr_t re
  */
 uintptr_t sha512_256_digest(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *data, uintptr_t data_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_256_digest_batch(uint8_t *result_ptr, uintptr_t result_size, const uint8_t *const *data_ptrs, const uintptr_t *data_sizes, uintptr_t count);
+
 /**
  * # Safety
  *
@@ -345,6 +458,20 @@ uintptr_t sha512_256_serialize(Sha512_256State *result_ptr, uintptr_t result_siz
  */
 uintptr_t sha512_256_deserialize(Sha512_256 *ctx, uintptr_t ctx_size, const Sha512_256State *state, uintptr_t state_size);
 
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_256_serialize_compact(uint8_t *result_ptr, uintptr_t result_size, const Sha512_256 *ctx);
+
+/**
+ * # Safety
+ *
+ * C bindings. Caller is responsible for ensuring memory correctness.
+ */
+uintptr_t sha512_256_deserialize_compact(Sha512_256 *ctx, uintptr_t ctx_size, const uint8_t *state, uintptr_t state_size);
+
 #ifdef __cplusplus
 }  /* extern "C" */
 #endif  /* __cplusplus */
@@ -98,6 +98,35 @@ typedef struct {
         } \
     } \
     \
+    /* Tests the batched `_digest_batch` function on every vector at once. */ \
+    static void GROUP_NAME##_test_batch(void **state) \
+    { \
+        test_state_t *ts = (test_state_t *)*state; \
+        vector_file_t *vf = ts->vf; \
+        size_t n = vf->vector_count; \
+        const uint8_t **ptrs = calloc(n, sizeof(*ptrs)); \
+        uintptr_t *sizes = calloc(n, sizeof(*sizes)); \
+        uint8_t *result_md = calloc(n, DIGEST_LEN_BYTES); \
+        size_t i; \
+        assert_non_null(ptrs); \
+        assert_non_null(sizes); \
+        assert_non_null(result_md); \
+        for (i = 0; i < n; ++i) { \
+            ptrs[i] = vf->vectors[i].msg; \
+            sizes[i] = vf->vectors[i].msg_len_bytes; \
+        } \
+        assert_int_equal(ALG_LOWER##_digest_batch(NULL, 0, ptrs, sizes, n), n * DIGEST_LEN_BYTES); \
+        assert_int_equal(ALG_LOWER##_digest_batch(result_md, n * DIGEST_LEN_BYTES, ptrs, sizes, n), \
+                         n * DIGEST_LEN_BYTES); \
+        for (i = 0; i < n; ++i) { \
+            test_vector_t *v = &vf->vectors[i]; \
+            assert_memory_equal(result_md + i * DIGEST_LEN_BYTES, v->md, v->md_len_bytes); \
+        } \
+        free(result_md); \
+        free(sizes); \
+        free(ptrs); \
+    } \
+    \
     /* Tests the streaming API (`init`, `update`, `finalize`). */ \
     static void GROUP_NAME##_test_streaming(void **state) \
     { \
@@ -133,6 +162,7 @@ typedef struct {
     { \
         const struct CMUnitTest tests[] = { \
             cmocka_unit_test(GROUP_NAME##_test_oneshot), \
+            cmocka_unit_test(GROUP_NAME##_test_batch), \
             cmocka_unit_test(GROUP_NAME##_test_streaming), \
         }; \
         return cmocka_run_group_tests(tests, GROUP_NAME##_setup, GROUP_NAME##_teardown); \
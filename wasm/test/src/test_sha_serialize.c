@@ -126,12 +126,47 @@
         free(sha_state); \
     } \
     \
+    static void GROUP_NAME##_test_compact_round_trip(void **state) \
+    { \
+        static const uint8_t msg[] = "The quick brown fox jumps over the lazy dog"; \
+        size_t split = sizeof(msg) / 2; \
+        uint8_t expected_md[DIGEST_LEN_BYTES]; \
+        uint8_t result_md[DIGEST_LEN_BYTES]; \
+        uintptr_t sha_size = ALG_LOWER##_init(NULL, 0); \
+        uintptr_t full_size = ALG_LOWER##_serialize(NULL, 0, NULL); \
+        uintptr_t max_size = ALG_LOWER##_serialize_compact(NULL, 0, NULL); \
+        uintptr_t compact_size; \
+        ALG_UPPER *sha_state = malloc(sha_size); \
+        ALG_UPPER *restored_state = malloc(sha_size); \
+        uint8_t *serialized_state = malloc(max_size); \
+        (void) state; \
+        assert_non_null(sha_state); \
+        assert_non_null(restored_state); \
+        assert_non_null(serialized_state); \
+        ALG_LOWER##_digest(expected_md, DIGEST_LEN_BYTES, msg, sizeof(msg) - 1); \
+        assert_int_equal(ALG_LOWER##_init(sha_state, sha_size), sha_size); \
+        ALG_LOWER##_update(sha_state, msg, split); \
+        compact_size = ALG_LOWER##_serialize_compact(serialized_state, max_size, sha_state); \
+        assert_true(compact_size > 0); \
+        assert_true(compact_size < full_size); \
+        assert_int_equal(ALG_LOWER##_serialize_compact(serialized_state, compact_size - 1, sha_state), 0); \
+        assert_int_equal(ALG_LOWER##_deserialize_compact(restored_state, sha_size, serialized_state, compact_size - 1), 0); \
+        assert_int_equal(ALG_LOWER##_deserialize_compact(restored_state, sha_size, serialized_state, compact_size), sha_size); \
+        ALG_LOWER##_update(restored_state, msg + split, (sizeof(msg) - 1) - split); \
+        assert_int_equal(ALG_LOWER##_finalize(restored_state, result_md, DIGEST_LEN_BYTES), DIGEST_LEN_BYTES); \
+        assert_memory_equal(result_md, expected_md, DIGEST_LEN_BYTES); \
+        free(serialized_state); \
+        free(restored_state); \
+        free(sha_state); \
+    } \
+    \
     int run_##GROUP_NAME##_tests(void) \
     { \
         const struct CMUnitTest tests[] = { \
             cmocka_unit_test(GROUP_NAME##_test_round_trip), \
             cmocka_unit_test(GROUP_NAME##_test_empty_round_trip), \
             cmocka_unit_test(GROUP_NAME##_test_independence), \
+            cmocka_unit_test(GROUP_NAME##_test_compact_round_trip), \
         }; \
         return cmocka_run_group_tests(tests, NULL, NULL); \
     } \